@@ -0,0 +1,226 @@
+use std::convert::TryInto;
+
+use crate::types::*;
+
+// Bumped whenever the layout below changes
+const FORMAT_VERSION: u8 = 1;
+
+// Cell codes. Piece codes are followed by the shape as UTF-8.
+const EMPTY_RUN: u8 = 0;
+// Added to a color code for multi-character shapes, which are preceded by their byte length
+#[cfg(feature = "multi-char-fairy")]
+const LONG_SHAPE: u8 = 0x80;
+const WALL: u8 = 1;
+const TURN_BASE: u8 = 2;
+const DEAD_UNKNOWN: u8 = 6;
+const DEAD_BASE: u8 = 7;
+
+fn color_code(color: &Color) -> u8 {
+    match color {
+        Color::Turn(c) => TURN_BASE + usize::from(c) as u8,
+        Color::Dead(None) => DEAD_UNKNOWN,
+        Color::Dead(Some(c)) => DEAD_BASE + usize::from(c) as u8,
+    }
+}
+
+fn decode_color(code: u8) -> Result<Color, ()> {
+    match code {
+        TURN_BASE..=5 => Ok(Color::Turn(TurnColor::all()[(code - TURN_BASE) as usize])),
+        DEAD_UNKNOWN => Ok(Color::Dead(None)),
+        DEAD_BASE..=10 => Ok(Color::Dead(Some(
+            TurnColor::all()[(code - DEAD_BASE) as usize],
+        ))),
+        _ => Err(()),
+    }
+}
+
+fn bits(flags: &[bool; 4]) -> u8 {
+    flags
+        .iter()
+        .enumerate()
+        .fold(0, |acc, (i, &flag)| acc | ((flag as u8) << i))
+}
+
+fn flags(bits: u8) -> [bool; 4] {
+    [bits & 1 != 0, bits & 2 != 0, bits & 4 != 0, bits & 8 != 0]
+}
+
+// Reads from the front of a byte slice, failing if it runs out
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], ()> {
+        if self.0.len() < len {
+            return Err(());
+        }
+        let (front, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Ok(front)
+    }
+
+    fn byte(&mut self) -> Result<u8, ()> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn shape(&mut self) -> Result<char, ()> {
+        let first = *self.0.first().ok_or(())?;
+        let len = match first {
+            0x00..=0x7f => 1,
+            0xc0..=0xdf => 2,
+            0xe0..=0xef => 3,
+            0xf0..=0xf7 => 4,
+            _ => return Err(()),
+        };
+        let s = std::str::from_utf8(self.take(len)?).map_err(|_| ())?;
+        s.chars().next().ok_or(())
+    }
+}
+
+impl Board {
+    /// Encodes the board in a compact binary format, see [`Board::from_bytes`] for the reverse.
+    ///
+    /// The layout is:
+    ///   - 1 byte format version, currently 1. It changes whenever the layout does.
+    ///   - 1 byte turn, as the index used for per-player arrays.
+    ///   - 3 bytes for `dead`, `castling_king` and `castling_queen`, each with bit `i` set for player `i`.
+    ///   - 4 little endian `u16`s of `points`, then `draw_ply` as a little endian `u64`.
+    ///   - The extra options as they appear between the curly braces of a fen4, as a little endian
+    ///     `u32` byte length followed by that much UTF-8.
+    ///   - The squares in row-major order starting from a1. A run of empty squares is a 0 byte followed
+    ///     by the length of the run, a wall is a 1 byte, and a piece is its color code followed by the
+    ///     shape in UTF-8. The color codes are 2-5 for living players, 6 for dead pieces without a
+    ///     color, and 7-10 for dead pieces that remember their player. With the `multi-char-fairy`
+    ///     feature, a [`Piece::Fairy`] is its color code plus `0x80`, then the byte length of its
+    ///     shape as a little endian `u32`, then the shape.
+    ///
+    /// ```
+    /// # use fen4::Board;
+    /// let bytes = Board::default().to_bytes();
+    /// assert!(bytes.len() < Board::default().to_string().len());
+    /// assert_eq!(Board::from_bytes(&bytes), Ok(Board::default()));
+    /// ```
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = vec![FORMAT_VERSION, usize::from(self.turn) as u8];
+        out.push(bits(&self.dead));
+        out.push(bits(&self.castling_king));
+        out.push(bits(&self.castling_queen));
+        for points in &self.points {
+            out.extend_from_slice(&points.to_le_bytes());
+        }
+        out.extend_from_slice(&(self.draw_ply as u64).to_le_bytes());
+        let extra = self.extra_options.to_string();
+        out.extend_from_slice(&(extra.len() as u32).to_le_bytes());
+        out.extend_from_slice(extra.as_bytes());
+
+        let mut empties = 0u8;
+        for piece in self.board.iter().flatten() {
+            if piece.is_empty() {
+                empties += 1;
+                continue;
+            }
+            if empties != 0 {
+                out.extend_from_slice(&[EMPTY_RUN, empties]);
+                empties = 0;
+            }
+            match piece {
+                Piece::Empty => {}
+                Piece::Wall => out.push(WALL),
+                Piece::Normal(color, shape) => {
+                    out.push(color_code(color));
+                    let mut buffer = [0; 4];
+                    out.extend_from_slice(shape.encode_utf8(&mut buffer).as_bytes());
+                }
+                #[cfg(feature = "multi-char-fairy")]
+                Piece::Fairy(color, shape) => {
+                    out.push(LONG_SHAPE + color_code(color));
+                    out.extend_from_slice(&(shape.len() as u32).to_le_bytes());
+                    out.extend_from_slice(shape.as_bytes());
+                }
+            }
+        }
+        if empties != 0 {
+            out.extend_from_slice(&[EMPTY_RUN, empties]);
+        }
+        out
+    }
+
+    /// Decodes a board written by [`Board::to_bytes`].
+    ///
+    /// Fails if the version is not one this crate can read, the data is cut short or has bytes left
+    /// over, or any part of it is invalid.
+    #[allow(clippy::result_unit_err)]
+    pub fn from_bytes(bytes: &[u8]) -> Result<Board, ()> {
+        let mut reader = Reader(bytes);
+        if reader.byte()? != FORMAT_VERSION {
+            return Err(());
+        }
+        let turn = *TurnColor::all().get(reader.byte()? as usize).ok_or(())?;
+        let dead = flags(reader.byte()?);
+        let castling_king = flags(reader.byte()?);
+        let castling_queen = flags(reader.byte()?);
+        let mut points = [0; 4];
+        for p in points.iter_mut() {
+            *p = u16::from_le_bytes(reader.take(2)?.try_into().unwrap());
+        }
+        let draw_ply = u64::from_le_bytes(reader.take(8)?.try_into().unwrap());
+        let extra_len = u32::from_le_bytes(reader.take(4)?.try_into().unwrap());
+        let extra = std::str::from_utf8(reader.take(extra_len as usize)?).map_err(|_| ())?;
+        let extra_options = if extra.is_empty() {
+            Extra::default()
+        } else {
+            format!("{{{}}}", extra).parse().map_err(|_| ())?
+        };
+
+        let mut board = Board {
+            turn,
+            dead,
+            castling_king,
+            castling_queen,
+            points,
+            draw_ply: draw_ply.try_into().map_err(|_| ())?,
+            extra_options,
+            board: Default::default(),
+        };
+        let mut index = 0;
+        while index < BOARD_SIZE * BOARD_SIZE {
+            let square = &mut board.board[index / BOARD_SIZE][index % BOARD_SIZE];
+            let code = reader.byte()?;
+            #[cfg(feature = "multi-char-fairy")]
+            {
+                if code >= LONG_SHAPE {
+                    let color = decode_color(code - LONG_SHAPE)?;
+                    let len = u32::from_le_bytes(reader.take(4)?.try_into().unwrap());
+                    let shape = std::str::from_utf8(reader.take(len as usize)?).map_err(|_| ())?;
+                    if shape.chars().count() < 2 {
+                        return Err(());
+                    }
+                    *square = Piece::Fairy(color, shape.to_string());
+                    index += 1;
+                    continue;
+                }
+            }
+            let color = match code {
+                EMPTY_RUN => {
+                    let run = reader.byte()? as usize;
+                    if run == 0 {
+                        return Err(());
+                    }
+                    index += run;
+                    continue;
+                }
+                WALL => {
+                    *square = Piece::Wall;
+                    index += 1;
+                    continue;
+                }
+                code => decode_color(code)?,
+            };
+            *square = Piece::Normal(color, reader.shape()?);
+            index += 1;
+        }
+        if index != BOARD_SIZE * BOARD_SIZE || !reader.0.is_empty() {
+            return Err(());
+        }
+        Ok(board)
+    }
+}
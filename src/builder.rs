@@ -0,0 +1,87 @@
+use crate::types::*;
+
+/// Builds a [`Board`] one setting at a time.
+///
+/// The builder starts from [`Board::empty`], or from an existing board with `BoardBuilder::from`.
+///
+/// ```
+/// # use fen4::{BoardBuilder, Color, Piece, Position, TurnColor};
+/// let board = BoardBuilder::new()
+///     .turn(TurnColor::Yellow)
+///     .piece(Position { row: 0, col: 7 }, Piece::Normal(Color::Turn(TurnColor::Red), 'K'))
+///     .points([0, 5, 0, 0])
+///     .build();
+/// assert_eq!(board.turn, TurnColor::Yellow);
+/// assert_eq!(board.find_first(&Piece::Normal(Color::Turn(TurnColor::Red), 'K')), Some(Position { row: 0, col: 7 }));
+/// ```
+#[derive(Debug, Clone)]
+pub struct BoardBuilder {
+    board: Board,
+}
+
+impl BoardBuilder {
+    pub fn new() -> Self {
+        BoardBuilder {
+            board: Board::empty(),
+        }
+    }
+
+    pub fn turn(mut self, turn: TurnColor) -> Self {
+        self.board.turn = turn;
+        self
+    }
+
+    /// Puts `piece` on `pos`, replacing whatever was there.
+    ///
+    /// Panics if `pos` is off the board.
+    pub fn piece(mut self, pos: Position, piece: Piece) -> Self {
+        self.board.board[pos.row][pos.col] = piece;
+        self
+    }
+
+    pub fn dead(mut self, dead: [bool; 4]) -> Self {
+        self.board.dead = dead;
+        self
+    }
+
+    pub fn castling_king(mut self, castling_king: [bool; 4]) -> Self {
+        self.board.castling_king = castling_king;
+        self
+    }
+
+    pub fn castling_queen(mut self, castling_queen: [bool; 4]) -> Self {
+        self.board.castling_queen = castling_queen;
+        self
+    }
+
+    pub fn points(mut self, points: [u16; 4]) -> Self {
+        self.board.points = points;
+        self
+    }
+
+    pub fn draw_ply(mut self, draw_ply: usize) -> Self {
+        self.board.draw_ply = draw_ply;
+        self
+    }
+
+    pub fn extra_options(mut self, extra_options: Extra) -> Self {
+        self.board.extra_options = extra_options;
+        self
+    }
+
+    pub fn build(self) -> Board {
+        self.board
+    }
+}
+
+impl Default for BoardBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<Board> for BoardBuilder {
+    fn from(board: Board) -> Self {
+        BoardBuilder { board }
+    }
+}
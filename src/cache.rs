@@ -0,0 +1,67 @@
+use std::collections::HashMap;
+
+use crate::moves::Move;
+use crate::types::*;
+
+/// Remembers the results of [`Board::legal_moves`] so asking again about the same board is cheap.
+///
+/// Results are keyed by [`Board::position_hash`] and the color, so changing the board (making a
+/// move or editing a square) gives a new key and the old result is simply not found again. Nothing
+/// has to be invalidated by hand, but the cache grows with every distinct position it is asked
+/// about; use [`MoveCache::clear`] to free it.
+///
+/// Each entry keeps the board it was computed for. A lookup only reuses it when the squares and
+/// the tags that change which moves are legal (`royal` and `pawnsBaseRank`) are the same, so hash
+/// collisions and boards that only differ in those tags compute fresh moves instead of returning
+/// the wrong ones.
+///
+/// ```
+/// # use fen4::{Board, MoveCache, TurnColor};
+/// let board = Board::default();
+/// let mut cache = MoveCache::new();
+/// assert_eq!(cache.cached_legal_moves(&board, TurnColor::Red), &board.legal_moves(TurnColor::Red)[..]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MoveCache {
+    moves: HashMap<(u64, TurnColor), (Board, Vec<Move>)>,
+}
+
+// Whether `legal_moves` gives the same result for both boards
+fn same_moves(a: &Board, b: &Board) -> bool {
+    a.board == b.board
+        && a.extra_options.royal == b.extra_options.royal
+        && a.extra_options.pawnbaserank == b.extra_options.pawnbaserank
+}
+
+impl MoveCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The same moves as [`Board::legal_moves`], only computed the first time this position and color are seen
+    pub fn cached_legal_moves(&mut self, board: &Board, color: TurnColor) -> &[Move] {
+        let compute = || (board.clone(), board.legal_moves(color));
+        let entry = self
+            .moves
+            .entry((board.position_hash(), color))
+            .or_insert_with(compute);
+        if !same_moves(&entry.0, board) {
+            *entry = compute();
+        }
+        &entry.1
+    }
+
+    /// Number of position and color pairs stored
+    pub fn len(&self) -> usize {
+        self.moves.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.moves.is_empty()
+    }
+
+    /// Forgets every stored result
+    pub fn clear(&mut self) {
+        self.moves.clear();
+    }
+}
@@ -1,5 +1,7 @@
-use crate::{Color, Position, TurnColor};
+use crate::{Board, Color, Piece, PieceKind, Position, TurnColor};
 use std::convert::From;
+use std::iter::FromIterator;
+use thiserror::Error;
 
 impl From<(usize, usize)> for Position {
     fn from(other: (usize, usize)) -> Self {
@@ -45,13 +47,202 @@ impl From<&TurnColor> for usize {
 }
 impl From<usize> for Color {
     fn from(index: usize) -> Self {
-        use TurnColor::*;
-        Color::Turn(match index {
-            0 => Red,
-            1 => Blue,
-            2 => Yellow,
-            3 => Green,
-            _ => return Color::Dead(None),
-        })
+        match TurnColor::all().get(index) {
+            Some(&tc) => Color::Turn(tc),
+            None => Color::Dead(None),
+        }
+    }
+}
+
+impl From<char> for PieceKind {
+    fn from(shape: char) -> Self {
+        use PieceKind::*;
+        match shape {
+            'P' => Pawn,
+            'N' => Knight,
+            'B' => Bishop,
+            'R' => Rook,
+            'Q' => Queen,
+            'K' => King,
+            c => Fairy(c),
+        }
+    }
+}
+impl From<PieceKind> for char {
+    fn from(kind: PieceKind) -> Self {
+        use PieceKind::*;
+        match kind {
+            Pawn => 'P',
+            Knight => 'N',
+            Bishop => 'B',
+            Rook => 'R',
+            Queen => 'Q',
+            King => 'K',
+            Fairy(c) => c,
+        }
+    }
+}
+
+/// Places each piece on an empty board with the metadata of [`Board::empty`]. When a position
+/// appears more than once the last piece wins.
+///
+/// # Panics
+/// Panics if a position is not on the board.
+impl FromIterator<(Position, Piece)> for Board {
+    fn from_iter<I: IntoIterator<Item = (Position, Piece)>>(iter: I) -> Self {
+        let mut board = Board::empty();
+        for (pos, piece) in iter {
+            board.board[pos.row][pos.col] = piece;
+        }
+        board
+    }
+}
+
+/// Reasons a [`Board`] can't be written as a standard chess FEN, see [`Board::to_standard_fen_2pc`]
+#[derive(Error, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ConversionError {
+    #[error("The board is not a two player (std2pc) board")]
+    NotStd2pc,
+    #[error("The piece on {0} is outside of the 8x8 area")]
+    OutsideArea(Position),
+    #[error("The piece on {0} can't be written in standard chess")]
+    UnsupportedPiece(Position),
+    #[error("It is {0}'s turn, but only Red and Yellow play in a two player game")]
+    UnsupportedTurn(TurnColor),
+}
+
+/// The first row of the 8x8 area used by [`Board::to_standard_fen_2pc`]
+const STD_FIRST_ROW: usize = 0;
+/// The first column of the 8x8 area used by [`Board::to_standard_fen_2pc`]
+const STD_FIRST_COL: usize = 3;
+
+// Whether `pos` is in the 8x8 area played on by two player (`std2pc`) boards
+pub(crate) fn in_std2pc_area(pos: Position) -> bool {
+    standard_square(pos).is_some()
+}
+
+fn standard_square(pos: Position) -> Option<String> {
+    let row = pos.row.checked_sub(STD_FIRST_ROW).filter(|&r| r < 8)?;
+    let col = pos.col.checked_sub(STD_FIRST_COL).filter(|&c| c < 8)?;
+    Some(format!("{}{}", char::from(b'a' + col as u8), row + 1))
+}
+
+impl Board {
+    /// Writes the lower Red/Yellow area of a two player (`std2pc`) board as a standard chess FEN.
+    ///
+    /// The 8x8 area is ranks 1-8 and files d-k of the fen4 board. Files d-k become files a-h and the
+    /// ranks keep their numbers, so Red's king on h1 is written on e1 like white's. Red pieces are
+    /// written as white (uppercase) and Yellow pieces as black (lowercase).
+    ///
+    /// The rest of the FEN is filled in as:
+    ///   - Active color: `w` for Red and `b` for Yellow.
+    ///   - Castling: Red's and Yellow's castling rights as `KQkq`. A right is only written when the
+    ///     king is on e1 (e8 for Yellow) and the rook on that side is in its corner, since a FEN
+    ///     can't have castling rights without them.
+    ///   - En passant: the capture square from Red's or Yellow's `enpassant` if it is in the area.
+    ///   - Halfmove clock: `draw_ply`.
+    ///   - Fullmove number: always 1 since fen4 doesn't keep track of it.
+    ///
+    /// Fails if the board is not marked `std2pc`, if it is not Red's or Yellow's turn, if any piece
+    /// is outside the area, or if a square in the area holds a wall or anything other than a living
+    /// Red or Yellow standard piece. Walls outside the area are fine, since they are how the unused
+    /// part of a two player board is usually blocked off.
+    ///
+    /// ```
+    /// # use fen4::{Board, Color, Piece, Position, TurnColor};
+    /// let mut board = Board::empty()
+    ///     .with_piece(Position { row: 0, col: 7 }, Piece::Normal(Color::Turn(TurnColor::Red), 'K'))
+    ///     .with_piece(Position { row: 7, col: 7 }, Piece::Normal(Color::Turn(TurnColor::Yellow), 'K'));
+    /// board.extra_options.std2pc = true;
+    /// // Board::empty() has all castling rights, but there are no rooks to castle with
+    /// assert_eq!(board.to_standard_fen_2pc().unwrap(), "4k3/8/8/8/8/8/8/4K3 w - - 0 1");
+    /// ```
+    pub fn to_standard_fen_2pc(&self) -> Result<String, ConversionError> {
+        if !self.extra_options.std2pc {
+            return Err(ConversionError::NotStd2pc);
+        }
+        let active = match self.turn {
+            TurnColor::Red => 'w',
+            TurnColor::Yellow => 'b',
+            other => return Err(ConversionError::UnsupportedTurn(other)),
+        };
+        if let Some((pos, _)) = self
+            .pieces()
+            .find(|(pos, piece)| piece.is_piece() && standard_square(*pos).is_none())
+        {
+            return Err(ConversionError::OutsideArea(pos));
+        }
+
+        let mut placement = String::new();
+        for row in (STD_FIRST_ROW..STD_FIRST_ROW + 8).rev() {
+            let mut empty = 0;
+            for col in STD_FIRST_COL..STD_FIRST_COL + 8 {
+                let pos = Position { row, col };
+                let letter = match &self.board[row][col] {
+                    Piece::Empty => {
+                        empty += 1;
+                        continue;
+                    }
+                    Piece::Normal(Color::Turn(color), shape) if "PNBRQK".contains(*shape) => {
+                        match color {
+                            TurnColor::Red => *shape,
+                            TurnColor::Yellow => shape.to_ascii_lowercase(),
+                            _ => return Err(ConversionError::UnsupportedPiece(pos)),
+                        }
+                    }
+                    _ => return Err(ConversionError::UnsupportedPiece(pos)),
+                };
+                if empty != 0 {
+                    placement.push_str(&empty.to_string());
+                    empty = 0;
+                }
+                placement.push(letter);
+            }
+            if empty != 0 {
+                placement.push_str(&empty.to_string());
+            }
+            if row != STD_FIRST_ROW {
+                placement.push('/');
+            }
+        }
+
+        // Each right needs the flag, the king on e1/e8 and the rook in that side's corner
+        let (king, queen) = (&self.castling_king, &self.castling_queen);
+        let (bottom, top) = (STD_FIRST_ROW, STD_FIRST_ROW + 7);
+        let (left, right) = (STD_FIRST_COL, STD_FIRST_COL + 7);
+        let rights = [
+            (TurnColor::Red, king, bottom, right, 'K'),
+            (TurnColor::Red, queen, bottom, left, 'Q'),
+            (TurnColor::Yellow, king, top, right, 'k'),
+            (TurnColor::Yellow, queen, top, left, 'q'),
+        ];
+        let holds = |color: TurnColor, row: usize, col: usize, shape: char| {
+            self.board[row][col] == Piece::Normal(Color::Turn(color), shape)
+        };
+        let mut castling: String = rights
+            .iter()
+            .filter(|&&(color, flags, row, rook_col, _)| {
+                flags[usize::from(color)]
+                    && holds(color, row, STD_FIRST_COL + 4, 'K')
+                    && holds(color, row, rook_col, 'R')
+            })
+            .map(|&(.., letter)| letter)
+            .collect();
+        if castling.is_empty() {
+            castling.push('-');
+        }
+
+        let red = usize::from(TurnColor::Red);
+        let yellow = usize::from(TurnColor::Yellow);
+        let enpassant = [red, yellow]
+            .iter()
+            .filter_map(|&i| self.extra_options.enpassant[i])
+            .find_map(|(target, _)| standard_square(target))
+            .unwrap_or_else(|| "-".to_string());
+
+        Ok(format!(
+            "{} {} {} {} {} 1",
+            placement, active, castling, enpassant, self.draw_ply
+        ))
     }
 }
@@ -0,0 +1,144 @@
+use std::fmt;
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use crate::display::MetaDataHelper;
+use crate::from_str::MetaDataParseError;
+use crate::types::*;
+use crate::{PieceParseError, PositionParseError};
+
+/// The changes needed to turn one [`Board`] into another, see [`Board::delta_to`].
+///
+/// Only changed squares are stored. The metadata is stored as a whole, and only if any of it changed.
+///
+/// The compact string form is the fen4 metadata (empty if unchanged), a '|', and then a comma
+/// separated list of changed squares like `h4=rP`. Squares that became empty have nothing after the '='.
+/// ```text
+///     B-0,0,0,0-1,1,1,1-1,1,1,1-0,0,0,0-0-|h2=,h4=rP
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct BoardDelta {
+    // A board whose grid is unused, only the metadata matters
+    metadata: Option<Board>,
+    squares: Vec<(Position, Piece)>,
+}
+
+impl BoardDelta {
+    /// The changed squares and the piece now on each of them
+    pub fn squares(&self) -> &[(Position, Piece)] {
+        &self.squares
+    }
+
+    /// Checks if applying this delta would change nothing
+    pub fn is_empty(&self) -> bool {
+        self.metadata.is_none() && self.squares.is_empty()
+    }
+}
+
+impl Board {
+    /// Computes the changes that turn `self` into `other`.
+    ///
+    /// `a.apply_delta(&a.delta_to(&b))` makes `a` equal to `b`.
+    pub fn delta_to(&self, other: &Board) -> BoardDelta {
+        // Compare everything except the grid by giving both boards the same grid
+        let mut metadata = other.clone();
+        metadata.board = self.board.clone();
+        let metadata = if metadata == *self {
+            None
+        } else {
+            metadata.board = Default::default();
+            Some(metadata)
+        };
+        let mut squares = Vec::new();
+        for (row, (mine, theirs)) in self.board.iter().zip(other.board.iter()).enumerate() {
+            for (col, (a, b)) in mine.iter().zip(theirs.iter()).enumerate() {
+                if a != b {
+                    squares.push((Position { row, col }, b.clone()));
+                }
+            }
+        }
+        BoardDelta { metadata, squares }
+    }
+
+    /// Applies the changes from [`Board::delta_to`].
+    pub fn apply_delta(&mut self, delta: &BoardDelta) {
+        if let Some(meta) = &delta.metadata {
+            let board = std::mem::take(&mut self.board);
+            *self = Board {
+                board,
+                ..meta.clone()
+            };
+        }
+        for (pos, piece) in &delta.squares {
+            self.board[pos.row][pos.col] = piece.clone();
+        }
+    }
+}
+
+impl fmt::Display for BoardDelta {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(meta) = &self.metadata {
+            write!(
+                f,
+                "{}",
+                MetaDataHelper {
+                    board: meta,
+                    preserve_order: false
+                }
+            )?;
+        }
+        write!(f, "|")?;
+        let mut first = true;
+        for (pos, piece) in &self.squares {
+            if !first {
+                write!(f, ",")?;
+            }
+            write!(f, "{}={}", pos, piece)?;
+            first = false;
+        }
+        Ok(())
+    }
+}
+
+/// Enum to store all ways [`BoardDelta`] can fail to parse
+#[derive(Error, Clone, PartialEq, Eq, Debug)]
+pub enum DeltaParseError {
+    #[error("A '|' should separate the metadata from the changed squares")]
+    NoSeparator,
+    #[error("Changed squares should look like 'h4=rP'")]
+    NoEquals,
+    #[error("The metadata failed to parse because of {0}")]
+    BadMetaData(#[from] MetaDataParseError),
+    #[error("A changed square failed to parse because of {0}")]
+    BadPosition(#[from] PositionParseError),
+    #[error("A changed piece failed to parse because of {0}")]
+    BadPiece(#[from] PieceParseError),
+}
+
+impl FromStr for BoardDelta {
+    type Err = DeltaParseError;
+    fn from_str(delta: &str) -> Result<Self, Self::Err> {
+        use DeltaParseError::*;
+        let separator = delta.rfind('|').ok_or(NoSeparator)?;
+        let (meta, squares_str) = (&delta[..separator], &delta[separator + 1..]);
+        let metadata = if meta.is_empty() {
+            None
+        } else {
+            Some(Board::from_meta_str(meta)?)
+        };
+        let mut squares = Vec::new();
+        if !squares_str.is_empty() {
+            for square in squares_str.split(',') {
+                let mut split = square.splitn(2, '=');
+                let pos = split.next().ok_or(NoEquals)?.parse()?;
+                let piece = match split.next().ok_or(NoEquals)? {
+                    "" => Piece::Empty,
+                    p => p.parse()?,
+                };
+                squares.push((pos, piece));
+            }
+        }
+        Ok(BoardDelta { metadata, squares })
+    }
+}
@@ -4,11 +4,10 @@ use crate::types::*;
 
 impl fmt::Display for Position {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if self.row >= 14 || self.col >= 14 {
+        if self.row >= BOARD_SIZE || self.col >= BOARD_SIZE {
             return Err(fmt::Error);
         }
-        let column_letter: char = ((self.col as u8) + b'a').into();
-        write!(f, "{}{}", column_letter, self.row + 1)
+        write!(f, "{}{}", self.column_char(), self.row_number())
     }
 }
 
@@ -57,16 +56,18 @@ impl fmt::Display for Piece {
             Piece::Empty => write!(f, ""),
             Piece::Wall => write!(f, "X"),
             Piece::Normal(color, shape) => write!(f, "{}{}", color, shape),
+            #[cfg(feature = "multi-char-fairy")]
+            Piece::Fairy(color, shape) => write!(f, "{}{}", color, shape),
         }
     }
 }
 
-struct BoardHelper<'a>(&'a [[Piece; 14]; 14]);
-struct RowHelper<'a>(&'a [Piece; 14]);
+struct BoardHelper<'a>(&'a [[Piece; BOARD_SIZE]; BOARD_SIZE]);
+struct RowHelper<'a>(&'a [Piece; BOARD_SIZE]);
 impl fmt::Debug for BoardHelper<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_list()
-            .entries(self.0.iter().map(|row| RowHelper(row)))
+            .entries(self.0.iter().map(RowHelper))
             .finish()
     }
 }
@@ -98,15 +99,94 @@ impl fmt::Debug for Board {
         out.finish()
     }
 }
-impl fmt::Display for Extra {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        fn royal_helper(f: &mut fmt::Formatter<'_>, value: &Option<Position>) -> fmt::Result {
-            if let Some(p) = value {
-                write!(f, "'{}'", p)
+// Labels of the known tags in the preferred order
+const PREFERRED_ORDER: [&str; 14] = [
+    "royal",
+    "lives",
+    CHECKS_TAG,
+    "resigned",
+    "flagged",
+    "stalemated",
+    "gameOver",
+    "zombieImmune",
+    "zombieType",
+    "enPassant",
+    "pawnsBaseRank",
+    "uniquify",
+    "std2pc",
+    ELIMINATED_TAG,
+];
+
+// Writes the tags of an Extra, either in the preferred order or in the order they were parsed in
+struct ExtraHelper<'a> {
+    extra: &'a Extra,
+    preserve_order: bool,
+}
+
+impl ExtraHelper<'_> {
+    // Whether a known tag has a value other than the default and should be written. When preserving
+    // the parsed form a boolean array tag with `null` slots is written even if it is all false.
+    fn is_set(&self, label: &str) -> bool {
+        let extra = self.extra;
+        let has_nulls =
+            |label| self.preserve_order && (0..4).any(|i| extra.nulls.is_null(label, i));
+        match label {
+            "royal" => extra.royal.iter().any(|squares| !squares.is_empty()),
+            "lives" => extra.lives.is_some(),
+            CHECKS_TAG => extra.checks.is_some(),
+            "resigned" => extra.resigned != [false; 4] || has_nulls(label),
+            "flagged" => extra.flagged != [false; 4] || has_nulls(label),
+            "stalemated" => extra.stalemated != [false; 4] || has_nulls(label),
+            "gameOver" => !extra.game_over.is_empty(),
+            "zombieImmune" => extra.zombie_immune != [false; 4] || has_nulls(label),
+            "zombieType" => extra.zombie_type != [""; 4],
+            "enPassant" => extra.enpassant != [None, None, None, None],
+            "pawnsBaseRank" => extra.pawnbaserank != 2,
+            "uniquify" => extra.uniquify != 0,
+            "std2pc" => extra.std2pc,
+            ELIMINATED_TAG => extra.eliminated != [0; 4],
+            _ => false,
+        }
+    }
+
+    // Whether any tag would be written, so the extra section isn't empty
+    fn has_tags(&self) -> bool {
+        !self.extra.unknown.is_empty() || PREFERRED_ORDER.iter().any(|label| self.is_set(label))
+    }
+
+    // Writes a boolean array tag, keeping `null` slots when preserving the parsed form
+    fn write_bools(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        label: &str,
+        values: &[bool; 4],
+    ) -> fmt::Result {
+        write!(f, "'{}':(", label)?;
+        for (i, value) in values.iter().enumerate() {
+            if i != 0 {
+                write!(f, ",")?;
+            }
+            if self.preserve_order && !value && self.extra.nulls.is_null(label, i) {
+                write!(f, "null")?;
             } else {
-                write!(f, "''")
+                write!(f, "{:?}", value)?;
             }
         }
+        write!(f, ")")
+    }
+
+    // Writes a known tag, whether or not it is set
+    fn write_tag(&self, f: &mut fmt::Formatter<'_>, label: &str) -> fmt::Result {
+        fn royal_helper(f: &mut fmt::Formatter<'_>, squares: &[Position]) -> fmt::Result {
+            write!(f, "'")?;
+            for (i, square) in squares.iter().enumerate() {
+                if i != 0 {
+                    write!(f, " ")?;
+                }
+                write!(f, "{}", square)?;
+            }
+            write!(f, "'")
+        }
 
         fn en_passant_helper(
             f: &mut fmt::Formatter<'_>,
@@ -118,206 +198,392 @@ impl fmt::Display for Extra {
                 write!(f, "''")
             }
         }
-        if *self != Extra::default() {
-            let mut comma = false;
-            if &self.royal != &[None, None, None, None] {
+        let extra = self.extra;
+        match label {
+            "royal" => {
                 write!(f, "'royal':(")?;
-                royal_helper(f, &self.royal[0])?;
+                royal_helper(f, &extra.royal[0])?;
                 write!(f, ",")?;
-                royal_helper(f, &self.royal[1])?;
+                royal_helper(f, &extra.royal[1])?;
                 write!(f, ",")?;
-                royal_helper(f, &self.royal[2])?;
+                royal_helper(f, &extra.royal[2])?;
                 write!(f, ",")?;
-                royal_helper(f, &self.royal[3])?;
+                royal_helper(f, &extra.royal[3])?;
                 write!(f, ")")?;
-                comma = true;
             }
-            if let Some(lives) = self.lives {
-                if comma {
-                    write!(f, ",")?;
-                }
+            "lives" => {
+                let lives = extra.lives.unwrap_or_default();
                 write!(
                     f,
                     "'lives':({},{},{},{})",
                     lives[0], lives[1], lives[2], lives[3]
                 )?;
-                comma = true;
             }
-            if self.resigned != [false; 4] {
-                if comma {
-                    write!(f, ",")?;
-                }
-                write!(
-                    f,
-                    "'resigned':({:?},{:?},{:?},{:?})",
-                    self.resigned[0], self.resigned[1], self.resigned[2], self.resigned[3]
-                )?;
-                comma = true;
-            }
-            if self.flagged != [false; 4] {
-                if comma {
-                    write!(f, ",")?;
-                }
-                write!(
-                    f,
-                    "'flagged':({:?},{:?},{:?},{:?})",
-                    self.flagged[0], self.flagged[1], self.flagged[2], self.flagged[3]
-                )?;
-                comma = true;
-            }
-            if self.stalemated != [false; 4] {
-                if comma {
-                    write!(f, ",")?;
-                }
+            CHECKS_TAG => {
+                let checks = extra.checks.unwrap_or_default();
                 write!(
                     f,
-                    "'stalemated':({:?},{:?},{:?},{:?})",
-                    self.stalemated[0], self.stalemated[1], self.stalemated[2], self.stalemated[3]
+                    "'{}':({},{},{},{})",
+                    CHECKS_TAG, checks[0], checks[1], checks[2], checks[3]
                 )?;
-                comma = true;
             }
-            if self.game_over != "" {
-                if comma {
-                    write!(f, ",")?;
-                }
-                write!(f, "'gameOver':'{}'", self.game_over)?;
-                comma = true;
-            }
-            if self.zombie_immune != [false; 4] {
-                if comma {
-                    write!(f, ",")?;
-                }
-                write!(
-                    f,
-                    "'zombieImmune':({:?},{:?},{:?},{:?})",
-                    self.zombie_immune[0],
-                    self.zombie_immune[1],
-                    self.zombie_immune[2],
-                    self.zombie_immune[3]
-                )?;
-                comma = true;
+            "resigned" => self.write_bools(f, label, &extra.resigned)?,
+            "flagged" => self.write_bools(f, label, &extra.flagged)?,
+            "stalemated" => self.write_bools(f, label, &extra.stalemated)?,
+            "gameOver" => {
+                write!(f, "'gameOver':'{}'", extra.game_over)?;
             }
-            if self.zombie_type != [""; 4] {
-                if comma {
-                    write!(f, ",")?;
-                }
+            "zombieImmune" => self.write_bools(f, label, &extra.zombie_immune)?,
+            "zombieType" => {
                 write!(
                     f,
                     "'zombieType':('{}','{}','{}','{}')",
-                    self.zombie_type[0],
-                    self.zombie_type[1],
-                    self.zombie_type[2],
-                    self.zombie_type[3]
+                    extra.zombie_type[0],
+                    extra.zombie_type[1],
+                    extra.zombie_type[2],
+                    extra.zombie_type[3]
                 )?;
-                comma = true;
             }
-            if &self.enpassant != &[None, None, None, None] {
-                if comma {
-                    write!(f, ",")?;
-                }
+            "enPassant" => {
                 write!(f, "'enPassant':(")?;
-                en_passant_helper(f, &self.enpassant[0])?;
+                en_passant_helper(f, &extra.enpassant[0])?;
                 write!(f, ",")?;
-                en_passant_helper(f, &self.enpassant[1])?;
+                en_passant_helper(f, &extra.enpassant[1])?;
                 write!(f, ",")?;
-                en_passant_helper(f, &self.enpassant[2])?;
+                en_passant_helper(f, &extra.enpassant[2])?;
                 write!(f, ",")?;
-                en_passant_helper(f, &self.enpassant[3])?;
+                en_passant_helper(f, &extra.enpassant[3])?;
                 write!(f, ")")?;
-                comma = true;
             }
-            if self.pawnbaserank != 2 {
-                if comma {
-                    write!(f, ",")?;
+            "pawnsBaseRank" => {
+                write!(f, "'pawnsBaseRank':{}", extra.pawnbaserank)?;
+            }
+            "uniquify" => {
+                write!(f, "'uniquify':{}", extra.uniquify)?;
+            }
+            "std2pc" => {
+                write!(f, "'std2pc':true")?;
+            }
+            ELIMINATED_TAG => {
+                let order = extra.eliminated;
+                write!(
+                    f,
+                    "'{}':({},{},{},{})",
+                    ELIMINATED_TAG, order[0], order[1], order[2], order[3]
+                )?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for ExtraHelper<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        enum Tag<'a> {
+            Known(&'a str),
+            Unknown(usize),
+        }
+        let unknown = &self.extra.unknown;
+        let mut known_used = [false; PREFERRED_ORDER.len()];
+        let mut unknown_used = vec![false; unknown.len()];
+        let mut order = Vec::new();
+        if self.preserve_order {
+            for label in self.extra.tag_order.labels() {
+                if let Some(i) = PREFERRED_ORDER.iter().position(|&l| l == label) {
+                    if !known_used[i] {
+                        known_used[i] = true;
+                        order.push(Tag::Known(PREFERRED_ORDER[i]));
+                    }
+                } else if let Some(i) =
+                    (0..unknown.len()).find(|&i| !unknown_used[i] && unknown[i].0 == label)
+                {
+                    unknown_used[i] = true;
+                    order.push(Tag::Unknown(i));
                 }
-                write!(f, "'pawnsBaseRank':{}", self.pawnbaserank)?;
-                comma = true;
             }
-            if self.uniquify != 0 {
-                if comma {
-                    write!(f, ",")?;
+        } else {
+            // Each unknown tag goes right after the recognized tag it followed when parsed
+            let mut anchors = vec![None; unknown.len()];
+            let mut last_known = None;
+            for label in self.extra.tag_order.labels() {
+                if let Some(i) = PREFERRED_ORDER.iter().position(|&l| l == label) {
+                    last_known = Some(i);
+                } else if let Some(i) =
+                    (0..unknown.len()).find(|&i| anchors[i].is_none() && unknown[i].0 == label)
+                {
+                    anchors[i] = Some(last_known);
                 }
-                write!(f, "'uniquify':{}", self.uniquify)?;
             }
-            if self.std2pc {
-                if comma {
-                    write!(f, ",")?;
+            let mut push_unknown = |order: &mut Vec<Tag>, anchor| {
+                for i in 0..unknown.len() {
+                    if anchors[i] == Some(anchor) {
+                        unknown_used[i] = true;
+                        order.push(Tag::Unknown(i));
+                    }
                 }
-                write!(f, "'std2pc':true")?;
+            };
+            push_unknown(&mut order, None);
+            for (i, label) in PREFERRED_ORDER.iter().enumerate() {
+                known_used[i] = true;
+                order.push(Tag::Known(label));
+                push_unknown(&mut order, Some(i));
+            }
+        }
+        for (i, label) in PREFERRED_ORDER.iter().enumerate() {
+            if !known_used[i] {
+                order.push(Tag::Known(label));
+            }
+        }
+        for (i, used) in unknown_used.iter().enumerate() {
+            if !used {
+                order.push(Tag::Unknown(i));
+            }
+        }
+
+        let mut comma = false;
+        for tag in order {
+            match tag {
+                Tag::Known(label) if !self.is_set(label) => continue,
+                _ if comma => write!(f, ",")?,
+                _ => {}
             }
+            match tag {
+                Tag::Known(label) => self.write_tag(f, label)?,
+                Tag::Unknown(i) => write!(f, "'{}':{}", unknown[i].0, unknown[i].1)?,
+            }
+            comma = true;
         }
         Ok(())
     }
 }
 
-impl fmt::Display for Board {
+impl fmt::Display for Extra {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let helper = ExtraHelper {
+            extra: self,
+            preserve_order: false,
+        };
+        write!(f, "{}", helper)
+    }
+}
+
+impl fmt::Display for ExtraValue {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        // Write out a line like: R-0,0,0,0-1,1,1,1-1,1,1,1-0,0,0,0-0-\n
+        match self {
+            ExtraValue::String(s) => write!(f, "'{}'", s),
+            ExtraValue::Number(n) => write!(f, "{}", n),
+            ExtraValue::Boolean(b) => write!(f, "{}", b),
+            ExtraValue::Array(values) => {
+                write!(f, "(")?;
+                let mut first = true;
+                for value in values {
+                    if !first {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", value)?;
+                    first = false;
+                }
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+// Writes just the metadata of a board, including the trailing dash
+pub(crate) struct MetaDataHelper<'a> {
+    pub(crate) board: &'a Board,
+    pub(crate) preserve_order: bool,
+}
+impl fmt::Display for MetaDataHelper<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Write out a line like: R-0,0,0,0-1,1,1,1-1,1,1,1-0,0,0,0-0-
         write!(
             f,
             "{}",
-            match self.turn {
+            match self.board.turn {
                 TurnColor::Red => "R",
                 TurnColor::Blue => "B",
                 TurnColor::Yellow => "Y",
                 TurnColor::Green => "G",
             }
         )?;
-        write!(f, "-{}", if self.dead[0] { "1" } else { "0" })?;
-        for d in &self.dead[1..] {
+        write!(f, "-{}", if self.board.dead[0] { "1" } else { "0" })?;
+        for d in &self.board.dead[1..] {
             write!(f, ",{}", if *d { "1" } else { "0" })?;
         }
-        write!(f, "-{}", if self.castling_king[0] { "1" } else { "0" })?;
-        for c in &self.castling_king[1..] {
+        write!(
+            f,
+            "-{}",
+            if self.board.castling_king[0] {
+                "1"
+            } else {
+                "0"
+            }
+        )?;
+        for c in &self.board.castling_king[1..] {
             write!(f, ",{}", if *c { "1" } else { "0" })?;
         }
-        write!(f, "-{}", if self.castling_queen[0] { "1" } else { "0" })?;
-        for c in &self.castling_queen[1..] {
+        write!(
+            f,
+            "-{}",
+            if self.board.castling_queen[0] {
+                "1"
+            } else {
+                "0"
+            }
+        )?;
+        for c in &self.board.castling_queen[1..] {
             write!(f, ",{}", if *c { "1" } else { "0" })?;
         }
-        write!(f, "-{}", self.points[0])?;
-        for p in &self.points[1..] {
+        write!(f, "-{}", self.board.points[0])?;
+        for p in &self.board.points[1..] {
             write!(f, ",{}", p)?;
         }
-        write!(f, "-{}-", self.draw_ply)?;
-        if self.extra_options != Extra::default() {
-            write!(f, "{{{}}}-", self.extra_options)?;
+        write!(f, "-{}-", self.board.draw_ply)?;
+        let extra = ExtraHelper {
+            extra: &self.board.extra_options,
+            preserve_order: self.preserve_order,
+        };
+        if extra.has_tags() {
+            write!(f, "{{{}}}-", extra)?;
+        }
+        Ok(())
+    }
+}
+
+/// The metadata always ends with a dash. When [`Extra`] is the default the extra section is left
+/// out entirely, so the metadata has 6 dashes instead of 7, and [`FromStr`](std::str::FromStr)
+/// accepts both forms.
+///
+/// The alternate flag (`{:#}`) writes the fen4 on a single line without any newlines.
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_board(self, f, false)
+    }
+}
+
+// Writes a board the same way as Display, but with the extra tags in the order they were parsed in
+struct OrderPreservingHelper<'a>(&'a Board);
+impl fmt::Display for OrderPreservingHelper<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_board(self.0, f, true)
+    }
+}
+
+impl Board {
+    /// Writes the fen4 like [`Display`](fmt::Display), but with the extra tags in the order they were
+    /// parsed in instead of the preferred order.
+    ///
+    /// Tags set with [`Board::set_extra`] come after the parsed ones in the order they were set, and
+    /// any other tags that were not in the parsed fen4 (for example fields assigned directly) follow
+    /// in the preferred order. Slots of boolean arrays that were parsed as `null` are written as `null` again unless
+    /// they have since been set to `true`. This is for tools that need to reproduce a file byte for byte.
+    ///
+    /// ```
+    /// # use fen4::Board;
+    /// let fen = "R-0,0,0,0-1,1,1,1-1,1,1,1-0,0,0,0-0-{'pawnsBaseRank':3,'lives':(2,2,2,2)}-14/14/14/14/14/14/14/14/14/14/14/14/14/14";
+    /// let board: Board = fen.parse().unwrap();
+    /// assert!(board.to_string_preserving_order().starts_with(
+    ///     "R-0,0,0,0-1,1,1,1-1,1,1,1-0,0,0,0-0-{'pawnsBaseRank':3,'lives':(2,2,2,2)}-"
+    /// ));
+    /// assert!(board.to_string().starts_with(
+    ///     "R-0,0,0,0-1,1,1,1-1,1,1,1-0,0,0,0-0-{'lives':(2,2,2,2),'pawnsBaseRank':3}-"
+    /// ));
+    /// ```
+    pub fn to_string_preserving_order(&self) -> String {
+        OrderPreservingHelper(self).to_string()
+    }
+}
+
+impl Board {
+    /// Draws the squares as a grid with two characters per square, the inverse of [`Board::from_ascii`].
+    ///
+    /// Rows are written top to bottom like the fen4 (rank 14 first), one per line, with the squares
+    /// separated by single spaces. Empty squares are `..` and walls are `XX`. Pieces are their color
+    /// followed by their shape, like in the fen4, except that dead pieces that remember their player
+    /// use that player's uppercase letter (`RK` instead of `drK`) so they still fit in two characters.
+    /// Multi-character [`Piece::Fairy`] pieces are the only ones that don't fit, and are written out
+    /// in full.
+    /// The metadata is not included.
+    ///
+    /// ```
+    /// # use fen4::Board;
+    /// let ascii = Board::default().to_ascii();
+    /// assert_eq!(ascii.lines().next(), Some(".. .. .. yR yN yB yK yQ yB yN yR .. .. .."));
+    /// ```
+    pub fn to_ascii(&self) -> String {
+        let mut out = String::new();
+        for row in self.rows_top_down() {
+            let cells: Vec<String> = row
+                .iter()
+                .map(|piece| match piece {
+                    Piece::Empty => "..".to_string(),
+                    Piece::Wall => "XX".to_string(),
+                    Piece::Normal(Color::Dead(Some(tc)), shape) => format!("{}{}", tc, shape),
+                    Piece::Normal(color, shape) => format!("{}{}", color, shape),
+                    #[cfg(feature = "multi-char-fairy")]
+                    Piece::Fairy(Color::Dead(Some(tc)), shape) => format!("{}{}", tc, shape),
+                    #[cfg(feature = "multi-char-fairy")]
+                    Piece::Fairy(color, shape) => format!("{}{}", color, shape),
+                })
+                .collect();
+            out.push_str(&cells.join(" "));
+            out.push('\n');
+        }
+        out
+    }
+}
+
+fn write_board(board: &Board, f: &mut fmt::Formatter<'_>, preserve_order: bool) -> fmt::Result {
+    let newlines = !f.alternate();
+    write!(
+        f,
+        "{}",
+        MetaDataHelper {
+            board,
+            preserve_order,
         }
-        write!(f, "\n")?;
+    )?;
+    if newlines {
+        writeln!(f)?;
+    }
 
-        // Write out 14 lines like: 3,yP,yP,yP,yP,yP,yP,yP,yP,3/\n
-        for i in (0..14).rev() {
-            let mut empties = 0;
-            for j in 0..13 {
-                match &self.board[i][j] {
-                    Piece::Empty => {
-                        empties += 1;
-                    }
-                    p => {
-                        if empties != 0 {
-                            write!(f, "{},", empties)?;
-                        }
-                        write!(f, "{},", p)?;
-                        empties = 0;
-                    }
-                }
-            }
-            match &self.board[i][13] {
+    // Write out BOARD_SIZE lines like: 3,yP,yP,yP,yP,yP,yP,yP,yP,3/\n
+    for i in (0..BOARD_SIZE).rev() {
+        let mut empties = 0;
+        for j in 0..BOARD_SIZE - 1 {
+            match &board.board[i][j] {
                 Piece::Empty => {
-                    write!(f, "{}", empties + 1)?;
+                    empties += 1;
                 }
                 p => {
                     if empties != 0 {
                         write!(f, "{},", empties)?;
                     }
-                    write!(f, "{}", p)?;
+                    write!(f, "{},", p)?;
+                    empties = 0;
                 }
             }
+        }
+        match &board.board[i][BOARD_SIZE - 1] {
+            Piece::Empty => {
+                write!(f, "{}", empties + 1)?;
+            }
+            p => {
+                if empties != 0 {
+                    write!(f, "{},", empties)?;
+                }
+                write!(f, "{}", p)?;
+            }
+        }
 
-            if i != 0 {
-                write!(f, "/\n")?;
+        if i != 0 {
+            write!(f, "/")?;
+            if newlines {
+                writeln!(f)?;
             }
         }
-        Ok(())
     }
+    Ok(())
 }
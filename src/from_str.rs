@@ -1,4 +1,4 @@
-use std::convert::TryInto;
+use std::convert::{TryFrom, TryInto};
 use std::num::ParseIntError;
 use std::str::FromStr;
 
@@ -11,24 +11,26 @@ use thiserror::Error;
 pub enum PositionParseError {
     #[error("'{0}' is not a valid column. Valid columns are 'a'-'n'")]
     ColumnInvalid(char),
-    #[error("'{0}' is not a valid row. Valid rows are 1-14")]
+    #[error("'{0}' is not a valid row. Valid rows are 1-{}", BOARD_SIZE)]
     RowInvalid(usize),
     #[error("All positions are between 2-3 characters long; This position is {0} characters long")]
     BadSize(usize),
     #[error("Row failed to parse as a number because {0}")]
     RowNotNumber(#[from] std::num::ParseIntError),
+    #[error("Position pairs should be two positions separated by a single ':'")]
+    BadColon,
 }
 
 impl FromStr for Position {
     type Err = PositionParseError;
     fn from_str(small: &str) -> Result<Self, Self::Err> {
         let len = small.len();
-        if len < 2 || len > 3 {
+        if !(2..=3).contains(&len) {
             return Err(PositionParseError::BadSize(len));
         }
         let mut iter = small.chars();
         let column_letter = iter.next().unwrap(); // Guaranteed to succeed because of `if len` above
-        if column_letter > 'n' || column_letter < 'a' {
+        if !('a'..='n').contains(&column_letter) {
             return Err(PositionParseError::ColumnInvalid(column_letter));
         }
 
@@ -39,18 +41,38 @@ impl FromStr for Position {
 
         let number_str = iter.as_str();
         let row = number_str.parse::<usize>()?;
-        if row == 0 || row > 14 {
+        if row == 0 || row > BOARD_SIZE {
             return Err(PositionParseError::RowInvalid(row));
         }
         Ok(Position { col, row: row - 1 })
     }
 }
 
+/// Parses two positions separated by a colon, like the `'i3:i4'` pairs of the `enPassant` tag.
+///
+/// ```
+/// # use fen4::{Position, PositionParseError};
+/// let pair = fen4::parse_position_pair("a1:a4");
+/// assert_eq!(pair, Ok((Position { row: 0, col: 0 }, Position { row: 3, col: 0 })));
+/// assert_eq!(fen4::parse_position_pair("a1-a4"), Err(PositionParseError::BadColon));
+/// ```
+pub fn parse_position_pair(pair: &str) -> Result<(Position, Position), PositionParseError> {
+    let mut split = pair.split(':');
+    let first = split.next().ok_or(PositionParseError::BadColon)?;
+    let second = split.next().ok_or(PositionParseError::BadColon)?;
+    if split.next().is_some() {
+        return Err(PositionParseError::BadColon);
+    }
+    Ok((first.parse()?, second.parse()?))
+}
+
 /// Enum to store all ways [`Piece`] can fail to parse
 #[derive(Error, Clone, Copy, Debug, PartialEq, Eq)]
 pub enum PieceParseError {
     #[error("Bad Color '{0}'. Only 'r', 'b', 'y', 'g', and 'd' are valid colors.")]
     BadColor(char),
+    /// The length is counted in characters, not bytes, so `"yαβ"` is 3 long even though `α` and `β`
+    /// take two bytes each.
     #[error("Bad Size {0}. Pieces like \"X\" , \"rK\" or \"drK\" are the only valid types. Longer strings are generally invalid and empty string is purposly left out.")]
     BadSize(usize),
 }
@@ -89,9 +111,15 @@ impl FromStr for Piece {
         } else {
             return Err(BadSize(0));
         };
-        let shape = iter.next().ok_or(BadSize(1))?;
-        if iter.next() != None {
-            return Err(BadSize(iter.count() + 3));
+        let shape = iter.next().ok_or_else(|| BadSize(small.chars().count()))?;
+        if iter.peek().is_some() {
+            #[cfg(feature = "multi-char-fairy")]
+            return Ok(Piece::Fairy(
+                color,
+                std::iter::once(shape).chain(iter).collect(),
+            ));
+            #[cfg(not(feature = "multi-char-fairy"))]
+            return Err(BadSize(small.chars().count()));
         }
         Ok(Piece::Normal(color, shape))
     }
@@ -129,32 +157,77 @@ fn fen4_point_helper(four_digits: &str) -> Result<[u16; 4], MetaDataParseError>
     Ok(tmp)
 }
 
+// Turns "R" into TurnColor::Red
+fn turn_helper(color_str: &str) -> Result<TurnColor, MetaDataParseError> {
+    match color_str {
+        "R" => Ok(TurnColor::Red),
+        "B" => Ok(TurnColor::Blue),
+        "Y" => Ok(TurnColor::Yellow),
+        "G" => Ok(TurnColor::Green),
+        _ => Err(MetaDataParseError::BadColor),
+    }
+}
+
+// The first `count` dash separated sections of a fen4, without looking at the rest of it
+fn leading_sections(fen: &str, count: usize) -> Result<Vec<&str>, MetaDataParseError> {
+    let fen = fen.trim_start_matches(|c: char| c.is_ascii_whitespace());
+    let mut sections: Vec<&str> = fen.splitn(count + 1, '-').collect();
+    if sections.len() <= count {
+        return Err(MetaDataParseError::TooFewSections {
+            needed: count,
+            found: sections.len() - 1,
+        });
+    }
+    sections.truncate(count);
+    Ok(sections)
+}
+
+/// Reads whose turn it is from the start of a fen4 without parsing the rest of it.
+///
+/// This is much cheaper than parsing a whole [`Board`] when the turn is all that is needed. Only
+/// the turn itself is checked, so a fen4 that is broken later on still succeeds. If the turn isn't
+/// followed by a dash this fails with [`MetaDataParseError::TooFewSections`].
+///
+/// ```
+/// # use fen4::TurnColor;
+/// assert_eq!(fen4::parse_turn("Y-0,0,0,0-1,1,1,1-1,1,1,1-0,0,0,0-0-"), Ok(TurnColor::Yellow));
+/// ```
+pub fn parse_turn(fen: &str) -> Result<TurnColor, MetaDataParseError> {
+    turn_helper(leading_sections(fen, 1)?[0])
+}
+
+/// Reads the points from the start of a fen4 without parsing the rest of it, see [`parse_turn`].
+///
+/// ```
+/// assert_eq!(fen4::parse_points("R-0,0,0,0-1,1,1,1-1,1,1,1-5,0,12,3-0-"), Ok([5, 0, 12, 3]));
+/// ```
+pub fn parse_points(fen: &str) -> Result<[u16; 4], MetaDataParseError> {
+    fen4_point_helper(leading_sections(fen, 5)?[4])
+}
+
 // Parses the entire metadata minus the last dash and makes a Board with that data filled in
 fn parse_meta(meta_data: &str) -> Result<Board, MetaDataParseError> {
     use MetaDataParseError::*;
     let mut meta_sections = meta_data.split("-");
+    let found = meta_data.split("-").count();
 
-    let color_str = meta_sections.next().ok_or(BadDash)?;
-    let turn = match color_str {
-        "R" => TurnColor::Red,
-        "B" => TurnColor::Blue,
-        "Y" => TurnColor::Yellow,
-        "G" => TurnColor::Green,
-        _ => return Err(BadColor),
-    };
+    let turn = turn_helper(meta_sections.next().ok_or(BadDash(found))?)?;
 
-    let dead = fen4_castle_helper(meta_sections.next().ok_or(BadDash)?)?;
-    let castling_king = fen4_castle_helper(meta_sections.next().ok_or(BadDash)?)?;
-    let castling_queen = fen4_castle_helper(meta_sections.next().ok_or(BadDash)?)?;
-    let points = fen4_point_helper(meta_sections.next().ok_or(BadDash)?)?;
-    let draw_ply = meta_sections.next().ok_or(BadDash)?.parse::<usize>()?;
+    let dead = fen4_castle_helper(meta_sections.next().ok_or(BadDash(found))?)?;
+    let castling_king = fen4_castle_helper(meta_sections.next().ok_or(BadDash(found))?)?;
+    let castling_queen = fen4_castle_helper(meta_sections.next().ok_or(BadDash(found))?)?;
+    let points = fen4_point_helper(meta_sections.next().ok_or(BadDash(found))?)?;
+    let draw_ply = meta_sections
+        .next()
+        .ok_or(BadDash(found))?
+        .parse::<usize>()?;
     let extra_options = if let Some(extra) = meta_sections.next() {
         extra.parse()?
     } else {
         Extra::default()
     };
-    if None != meta_sections.next() {
-        return Err(BadDash);
+    if meta_sections.next().is_some() {
+        return Err(BadDash(found));
     }
     Ok(Board {
         turn,
@@ -194,8 +267,14 @@ fn split_array(array: &str) -> Result<[&str; 4], MetaDataParseError> {
 /// Enum to store all ways [`Extra`] can fail to parse
 #[derive(Error, Clone, PartialEq, Eq, Debug)]
 pub enum MetaDataParseError {
-    #[error("There should be either 6 or 7 dashes in the metadata")]
-    BadDash,
+    /// The metadata doesn't have the right number of dash separated sections. The number found is
+    /// stored, counting each section that ends in a dash: the 6 fixed ones plus the optional tags.
+    #[error("Expected 6 or 7 metadata sections, found {0}")]
+    BadDash(usize),
+    /// [`parse_turn`] or [`parse_points`] needed the first `needed` sections, each ending in a dash,
+    /// but only `found` of them were there.
+    #[error("Expected at least {needed} metadata section(s), found {found}")]
+    TooFewSections { needed: usize, found: usize },
     #[error("There should be only two curly braces and they should only be present if there are tagged values")]
     BadCurly,
     #[error("All tags should be surrounded by single quotes")]
@@ -240,156 +319,246 @@ impl FromStr for Extra {
             let value_end = if current.starts_with('(') {
                 current.find(')').ok_or(BadParen)? + 1
             } else {
-                current.find(|c| c == ',' || c == '}').unwrap()
+                current.find([',', '}']).unwrap()
             };
             let (value, tmp) = current.split_at(value_end);
             current = tmp;
-            match label_trimmed {
-                "enPassant" => {
-                    let array = split_array(value)?;
-                    let mut i = 0;
-                    for pair in &array {
-                        if extras.enpassant[i] != None {
-                            return Err(RepeatedTag);
-                        }
-                        let trimmed = pair
-                            .strip_prefix('\'')
-                            .ok_or(BadQuote)?
-                            .strip_suffix('\'')
-                            .ok_or(BadQuote)?;
-                        if trimmed != "" {
-                            let mut split = trimmed.split(':');
-                            let first = split.next().ok_or(BadColon)?;
-                            let second = split.next().ok_or(BadColon)?;
-                            if split.next() != None {
-                                return Err(BadColon);
-                            }
-                            extras.enpassant[i] =
-                                Some((first.parse::<Position>()?, second.parse::<Position>()?));
-                        }
-                        i += 1;
+            extras.tag_order.push(label_trimmed);
+            match extras.parse_tag(label_trimmed, value) {
+                Err(UnknownTag(_)) => extras
+                    .unknown
+                    .push((label_trimmed.to_string(), value.to_string())),
+                result => result?,
+            }
+            if current == "}" {
+                break;
+            }
+            current = current.strip_prefix(',').ok_or(BadComma)?;
+        }
+        Ok(extras)
+    }
+}
+
+impl Extra {
+    // Parses a single tagged value and stores it in the matching field
+    fn parse_tag(&mut self, label: &str, value: &str) -> Result<(), MetaDataParseError> {
+        use MetaDataParseError::*;
+        match label {
+            "enPassant" => {
+                let array = split_array(value)?;
+                for (i, pair) in array.iter().enumerate() {
+                    if self.enpassant[i].is_some() {
+                        return Err(RepeatedTag);
                     }
-                }
-                "royal" | "kingSquares" => {
-                    let array = split_array(value)?;
-                    let mut i = 0;
-                    for position in &array {
-                        if extras.royal[i] != None {
-                            return Err(RepeatedTag);
-                        }
-                        let trimmed = position
-                            .strip_prefix('\'')
-                            .ok_or(BadQuote)?
-                            .strip_suffix('\'')
-                            .ok_or(BadQuote)?;
-                        if trimmed != "" {
-                            extras.royal[i] = Some(trimmed.parse::<Position>()?);
-                        }
-                        i += 1;
+                    let trimmed = pair
+                        .strip_prefix('\'')
+                        .ok_or(BadQuote)?
+                        .strip_suffix('\'')
+                        .ok_or(BadQuote)?;
+                    if !trimmed.is_empty() {
+                        self.enpassant[i] =
+                            Some(parse_position_pair(trimmed).map_err(|e| match e {
+                                PositionParseError::BadColon => BadColon,
+                                e => BadPosition(e),
+                            })?);
                     }
                 }
-                "pawnsBaseRank" | "uniquify" => {
-                    let number = value.parse::<usize>()?;
-                    if label_trimmed == "uniquify" {
-                        extras.uniquify = number;
-                    } else {
-                        extras.pawnbaserank = number;
+            }
+            "royal" | "kingSquares" => {
+                let array = split_array(value)?;
+                for (i, position) in array.iter().enumerate() {
+                    if !self.royal[i].is_empty() {
+                        return Err(RepeatedTag);
                     }
-                }
-                "resigned" | "flagged" | "stalemated" | "zombieImmune" => {
-                    let array = split_array(value)?;
-                    let mut i = 0;
-                    let output = if label_trimmed == "flagged" {
-                        &mut extras.flagged
-                    } else if label_trimmed == "resigned" {
-                        &mut extras.resigned
-                    } else if label_trimmed == "stalemated" {
-                        &mut extras.stalemated
-                    } else {
-                        &mut extras.zombie_immune
-                    };
-                    for truth in &array {
-                        if output[i] {
-                            return Err(RepeatedTag);
-                        }
-                        output[i] = match *truth {
-                            "true" => true,
-                            "false" => false,
-                            "null" => false,
-                            _ => return Err(BadBoolean),
-                        };
-                        i += 1;
+                    let trimmed = position
+                        .strip_prefix('\'')
+                        .ok_or(BadQuote)?
+                        .strip_suffix('\'')
+                        .ok_or(BadQuote)?;
+                    for square in trimmed.split_whitespace() {
+                        self.royal[i].push(square.parse::<Position>()?);
                     }
                 }
-                "std2pc" => {
-                    extras.std2pc = match value {
+            }
+            "pawnsBaseRank" | "uniquify" => {
+                let number = value.parse::<usize>()?;
+                if label == "uniquify" {
+                    self.uniquify = number;
+                } else {
+                    self.pawnbaserank = number;
+                }
+            }
+            "resigned" | "flagged" | "stalemated" | "zombieImmune" => {
+                let array = split_array(value)?;
+                let output = if label == "flagged" {
+                    &mut self.flagged
+                } else if label == "resigned" {
+                    &mut self.resigned
+                } else if label == "stalemated" {
+                    &mut self.stalemated
+                } else {
+                    &mut self.zombie_immune
+                };
+                for (i, truth) in array.iter().enumerate() {
+                    if output[i] {
+                        return Err(RepeatedTag);
+                    }
+                    output[i] = match *truth {
                         "true" => true,
                         "false" => false,
+                        "null" => {
+                            self.nulls.push(label, i);
+                            false
+                        }
                         _ => return Err(BadBoolean),
                     };
                 }
-                "lives" => {
-                    let array = split_array(value)?;
-                    let mut i = 0;
-                    if extras.lives != None {
+            }
+            "std2pc" => {
+                self.std2pc = match value {
+                    "true" => true,
+                    "false" => false,
+                    _ => return Err(BadBoolean),
+                };
+            }
+            "lives" | CHECKS_TAG => {
+                let array = split_array(value)?;
+                let output = if label == "lives" {
+                    &mut self.lives
+                } else {
+                    &mut self.checks
+                };
+                if output.is_some() {
+                    return Err(RepeatedTag);
+                }
+                let mut tmp = [0; 4];
+                for (i, count) in array.iter().enumerate() {
+                    tmp[i] = count.parse::<usize>()?;
+                }
+                *output = Some(tmp);
+            }
+            ELIMINATED_TAG => {
+                let array = split_array(value)?;
+                for (i, place) in array.iter().enumerate() {
+                    if self.eliminated[i] != 0 {
                         return Err(RepeatedTag);
                     }
-                    let mut tmp = [0; 4];
-                    for life in &array {
-                        tmp[i] = life.parse::<usize>()?;
-                        i += 1;
-                    }
-                    extras.lives = Some(tmp);
+                    self.eliminated[i] = place.parse::<usize>()?;
                 }
-                "zombieType" => {
-                    let array = split_array(value)?;
-                    let mut i = 0;
-                    for pair in &array {
-                        if extras.zombie_type[i] != "" {
-                            return Err(RepeatedTag);
-                        }
-                        let trimmed = pair
-                            .strip_prefix('\'')
-                            .ok_or(BadQuote)?
-                            .strip_suffix('\'')
-                            .ok_or(BadQuote)?;
-                        extras.zombie_type[i] = trimmed.into();
-                        i += 1;
+            }
+            "zombieType" => {
+                let array = split_array(value)?;
+                for (i, pair) in array.iter().enumerate() {
+                    if !self.zombie_type[i].is_empty() {
+                        return Err(RepeatedTag);
                     }
-                }
-                "gameOver" => {
-                    let trimmed = value
+                    let trimmed = pair
                         .strip_prefix('\'')
                         .ok_or(BadQuote)?
                         .strip_suffix('\'')
                         .ok_or(BadQuote)?;
-                    extras.game_over = trimmed.into();
-                }
-                s => {
-                    return Err(UnknownTag(String::from(s)));
+                    self.zombie_type[i] = trimmed.into();
                 }
             }
-            if current == "}" {
-                break;
+            "gameOver" => {
+                let trimmed = value
+                    .strip_prefix('\'')
+                    .ok_or(BadQuote)?
+                    .strip_suffix('\'')
+                    .ok_or(BadQuote)?;
+                self.game_over = trimmed.into();
+            }
+            s => {
+                return Err(UnknownTag(String::from(s)));
             }
-            current = current.strip_prefix(',').ok_or(BadComma)?;
         }
-        Ok(extras)
+        Ok(())
+    }
+
+    // Resets the field a tag is stored in back to its default
+    fn reset_tag(&mut self, label: &str) -> Result<(), MetaDataParseError> {
+        let default = Extra::default();
+        self.nulls.clear(label);
+        match label {
+            "enPassant" => self.enpassant = default.enpassant,
+            "royal" | "kingSquares" => self.royal = default.royal,
+            "pawnsBaseRank" => self.pawnbaserank = default.pawnbaserank,
+            "uniquify" => self.uniquify = default.uniquify,
+            "resigned" => self.resigned = default.resigned,
+            "flagged" => self.flagged = default.flagged,
+            "stalemated" => self.stalemated = default.stalemated,
+            "zombieImmune" => self.zombie_immune = default.zombie_immune,
+            "std2pc" => self.std2pc = default.std2pc,
+            "lives" => self.lives = default.lives,
+            CHECKS_TAG => self.checks = default.checks,
+            ELIMINATED_TAG => self.eliminated = default.eliminated,
+            "zombieType" => self.zombie_type = default.zombie_type,
+            "gameOver" => self.game_over = default.game_over,
+            s => return Err(MetaDataParseError::UnknownTag(String::from(s))),
+        }
+        Ok(())
+    }
+}
+
+impl Board {
+    /// Sets a single option in [`Extra`] by the tag name used in the fen4, replacing any previous value.
+    ///
+    /// The value is checked the same way the parser checks it, and on error `extra_options` is left unchanged.
+    /// A tag that wasn't set before is added to the end of [`Extra::tag_order`], so
+    /// [`Board::to_string_preserving_order`] writes it after the tags that were parsed.
+    ///
+    /// ```
+    /// # use fen4::{Board, ExtraValue};
+    /// let mut board = Board::default();
+    /// board.set_extra("pawnsBaseRank", ExtraValue::Number(8)).unwrap();
+    /// assert_eq!(board.extra_options.pawnbaserank, 8);
+    /// ```
+    pub fn set_extra(&mut self, tag: &str, value: ExtraValue) -> Result<(), MetaDataParseError> {
+        let mut extras = self.extra_options.clone();
+        extras.reset_tag(tag)?;
+        extras.parse_tag(tag, &value.to_string())?;
+        extras.tag_order.push_if_missing(tag);
+        self.extra_options = extras;
+        Ok(())
     }
 }
 
 /// Enum to store all ways [`Board`] can fail to parse
-#[derive(Clone, Debug, PartialEq, Eq)]
+///
+/// Rows and columns are stored as indices into [`Board::board`], so row 0 is the bottom row
+/// (rank 1) and column 0 is the a-file. The error messages use the labels shown on the board
+/// instead: ranks 1-14 and files a-n. [`BoardParseError::row`] and [`BoardParseError::rank`] give
+/// both forms.
+#[derive(Error, Clone, Debug, PartialEq, Eq)]
 pub enum BoardParseError {
+    #[error("No '-' was found in the fen. Fen4's should start with metadata about castling, turn, and more.")]
     NoDash,
-    BadMetaData(MetaDataParseError),
+    #[error("Something went wrong with metadata parsing: {0}")]
+    BadMetaData(#[from] MetaDataParseError),
+    #[error("{}", board_size_message(*.0, *.1))]
     BadBoardSize(BoardSize, usize),
+    /// A row with nothing between two commas, like `yR,,yN` or `yR, ,yN`.
+    ///
+    /// Spaces around a segment are fine (`3, yR, yN`), but a segment that is only whitespace is an
+    /// error, since it could be a piece that went missing. [`Board::parse_lenient`] skips them instead.
+    #[error("Segment at {} is empty which is not valid.", square_name(*.0, *.1))]
     EmptySegment(usize, usize),
-    BadSegmentNumber(usize, usize, ParseIntError),
-    BadSegmentPiece(usize, usize, PieceParseError),
+    #[error(
+        "Segment at {} starts with a digit but cannot be parsed as a number because of {2}",
+        square_name(*.0, *.1)
+    )]
+    BadSegmentNumber(usize, usize, #[source] ParseIntError),
+    #[error("Segment at {} cannot be parsed as piece because of {2}", square_name(*.0, *.1))]
+    BadSegmentPiece(usize, usize, #[source] PieceParseError),
+    /// The segments of row `.0` add up to more than 14 columns. `.1` is the index of the first
+    /// segment that doesn't fit, counting the row's comma separated segments from 0.
+    #[error("Row {} describes more than {} columns; segment {1} (counting from 0) goes past the last column.", .0 + 1, BOARD_SIZE)]
+    ColumnOverflow(usize, usize),
 }
 
+/// Ways the board can have the wrong dimensions, used by [`BoardParseError::BadBoardSize`]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[allow(clippy::enum_variant_names)]
 pub enum BoardSize {
     TooManyColumns,
     TooFewColumns,
@@ -397,82 +566,340 @@ pub enum BoardSize {
     TooFewRows,
 }
 
-impl std::fmt::Display for BoardParseError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+// The algebraic name of a square, like "c2"
+fn square_name(row: usize, col: usize) -> String {
+    format!("{}{}", char::from(b'a' + col as u8), row + 1)
+}
+
+impl BoardParseError {
+    /// The index into [`Board::board`] of the row the error is in, if it is about a single row.
+    ///
+    /// ```
+    /// # use fen4::{Board, BoardParseError};
+    /// let fen = "R-0,0,0,0-0,0,0,0-0,0,0,0-0,0,0,0-0-13/14/14/14/14/14/14/14/14/14/14/14/14/14";
+    /// let err = fen.parse::<Board>().unwrap_err();
+    /// assert_eq!(err.row(), Some(13));
+    /// assert_eq!(err.rank(), Some(14));
+    /// assert_eq!(err.to_string(), "Not enough columns in row 14.");
+    /// ```
+    pub fn row(&self) -> Option<usize> {
         use BoardParseError::*;
-        use BoardSize::*;
         match self {
-            NoDash => write!(f,"No '-' was found in the fen. Fen4's should start with metadata about castling, turn, and more."),
-            BadMetaData(me) => write!(f,"Something went wrong with metadata parsing: {}",me),
-            BadBoardSize(bs,row) => match bs {
-                TooManyColumns => write!(f,"Too many columns in row {}.",row),
-                TooFewColumns=> write!(f,"Not enough columns in row {}.", row),
-                TooManyRows=> write!(f,"Too many rows overall. Make sure there is not a leading or trailing '/'"),
-                TooFewRows=> write!(f,"{} too few rows overall. Make sure there is not a missing row.",row),
-            },
-            EmptySegment(row,col)=> write!(f,"Segment at ({},{}) is empty which is not valid.",row,col), 
-            BadSegmentNumber(row,col,int_error) => write!(f,"Segment at ({},{}) starts with a digit but cannot be parsed as a number because of {}",row,col,int_error),
-            BadSegmentPiece(row,col,piece_error) => write!(f,"Segment at ({},{}) cannot be parsed as piece because of {}",row,col,piece_error),
+            BadBoardSize(BoardSize::TooManyColumns, row)
+            | BadBoardSize(BoardSize::TooFewColumns, row)
+            | EmptySegment(row, _)
+            | BadSegmentNumber(row, _, _)
+            | BadSegmentPiece(row, _, _)
+            | ColumnOverflow(row, _) => Some(*row),
+            _ => None,
         }
     }
+
+    /// The rank (1-14, counting up from Red's side) of [`BoardParseError::row`], as used in the error messages
+    pub fn rank(&self) -> Option<usize> {
+        self.row().map(|row| row + 1)
+    }
 }
 
-impl std::error::Error for BoardParseError {}
+fn board_size_message(size: BoardSize, row: usize) -> String {
+    use BoardSize::*;
+    match size {
+        TooManyColumns => format!("Too many columns in row {}.", row + 1),
+        TooFewColumns => format!("Not enough columns in row {}.", row + 1),
+        TooManyRows => {
+            "Too many rows overall. Make sure there is not a leading or trailing '/'".to_string()
+        }
+        TooFewRows => format!(
+            "{} too few rows overall. Make sure there is not a missing row.",
+            row
+        ),
+    }
+}
 
 impl FromStr for Board {
     type Err = BoardParseError;
     fn from_str(fen: &str) -> Result<Self, Self::Err> {
-        use BoardParseError::*;
-        use BoardSize::*;
-        let last_dash = if let Some(tmp) = fen.rfind("-") {
-            tmp
-        } else {
-            return Err(NoDash);
+        first_error(parse_board(fen, ParseOptions::default()))
+    }
+}
+
+impl TryFrom<&str> for Board {
+    type Error = BoardParseError;
+    fn try_from(fen: &str) -> Result<Self, Self::Error> {
+        fen.parse()
+    }
+}
+
+impl TryFrom<String> for Board {
+    type Error = BoardParseError;
+    fn try_from(fen: String) -> Result<Self, Self::Error> {
+        fen.parse()
+    }
+}
+
+impl Board {
+    /// Parses a fen4 the same way as [`FromStr`], but accepts some deviations from the format seen in the wild.
+    ///
+    /// Currently the differences are:
+    ///   - If the board section contains no '/' at all, rows are separated by newlines instead.
+    ///     When any '/' is present it is always the row separator and newlines are just whitespace.
+    ///   - Rows may start with a row number followed by a colon, like `14: 3,yR,...`. The number is
+    ///     only a label and is not checked against the row's actual position.
+    ///   - Empty segments, like the middle of `yR,,yN` or `yR, ,yN`, are skipped as if the extra comma
+    ///     wasn't there instead of being [`BoardParseError::EmptySegment`] errors.
+    ///
+    /// ```
+    /// # use fen4::Board;
+    /// let fen = "R-0,0,0,0-0,0,0,0-0,0,0,0-0,0,0,0-0-\n14\n14\n14\n14\n14\n14\n14\n14\n14\n14\n14\n14\n14\n14";
+    /// assert!(fen.parse::<Board>().is_err());
+    /// assert!(Board::parse_lenient(fen).is_ok());
+    /// ```
+    pub fn parse_lenient(fen: &str) -> Result<Board, BoardParseError> {
+        first_error(parse_board(
+            fen,
+            ParseOptions {
+                lenient: true,
+                ..ParseOptions::default()
+            },
+        ))
+    }
+
+    /// Parses only the metadata of a fen4, leaving every square [`Piece::Empty`].
+    ///
+    /// `meta` is everything before the board, with or without the final dash.
+    ///
+    /// ```
+    /// # use fen4::{Board, TurnColor};
+    /// let board = Board::from_meta_str("G-0,0,0,0-1,1,1,1-1,1,1,1-0,3,0,0-2-").unwrap();
+    /// assert_eq!(board.turn, TurnColor::Green);
+    /// assert_eq!(board.points, [0, 3, 0, 0]);
+    /// ```
+    pub fn from_meta_str(meta: &str) -> Result<Board, MetaDataParseError> {
+        let meta = meta.trim_matches(|c: char| c.is_ascii_whitespace());
+        parse_meta(meta.strip_suffix('-').unwrap_or(meta))
+    }
+
+    /// Parses as much of a fen4 as possible instead of stopping at the first problem.
+    ///
+    /// Every error that [`FromStr`] could have reported is collected, in the order they occur, and
+    /// the parts that failed are left out:
+    ///   - Bad metadata leaves all the metadata as in [`Board::default`].
+    ///   - A segment that isn't a valid piece leaves its square empty.
+    ///   - Empty or unparsable numeric segments are skipped.
+    ///   - Segments past the end of a row and rows past the bottom of the board are ignored.
+    ///
+    /// If the list of errors is empty, the board is exactly what [`FromStr`] would return.
+    pub fn parse_best_effort(fen: &str) -> (Board, Vec<BoardParseError>) {
+        parse_board(fen, ParseOptions::default())
+    }
+
+    /// Parses a fen4 like [`Board::parse_best_effort`], but keeps squares with pieces that can't be
+    /// parsed, such as multi-letter shapes without the `multi-char-fairy` feature, as a `'?'` piece
+    /// instead of leaving them empty.
+    ///
+    /// This is lossy: the original shape is thrown away, and if the color can't be read either the
+    /// placeholder is `Piece::Normal(Color::Dead(None), '?')`. Any other problems are ignored the
+    /// same way [`Board::parse_best_effort`] ignores them, so a board is always returned.
+    ///
+    /// ```
+    /// # use fen4::{Board, Color, Piece, TurnColor};
+    /// let fen = "R-0,0,0,0-0,0,0,0-0,0,0,0-0,0,0,0-0-14/14/14/14/14/14/14/14/14/14/14/14/14/3,rAmazon,10";
+    /// let board = Board::from_fen_lossy(fen);
+    /// # #[cfg(not(feature = "multi-char-fairy"))]
+    /// assert_eq!(board.board[0][3], Piece::Normal(Color::Turn(TurnColor::Red), '?'));
+    /// ```
+    pub fn from_fen_lossy(fen: &str) -> Board {
+        let options = ParseOptions {
+            lossy: true,
+            ..ParseOptions::default()
         };
+        parse_board(fen, options).0
+    }
+}
 
-        let meta_data = &fen[..last_dash];
-        let board = &fen[last_dash + 1..];
+// Parses one two character square of the diagram written by Board::to_ascii
+fn parse_ascii_cell(cell: &str) -> Result<Piece, PieceParseError> {
+    let mut chars = cell.chars();
+    let color = chars.next();
+    let shape = chars.as_str();
+    let shape_len = shape.chars().count();
+    let fits = shape_len == 1 || (cfg!(feature = "multi-char-fairy") && shape_len > 1);
+    let color = match color {
+        Some(color) if fits => color,
+        _ => return Err(PieceParseError::BadSize(cell.chars().count())),
+    };
+    match (color, shape) {
+        ('.', ".") => Ok(Piece::Empty),
+        ('X', "X") => Ok(Piece::Wall),
+        // Dead pieces that remember their player use its uppercase letter
+        ('R', _) | ('B', _) | ('Y', _) | ('G', _) => {
+            format!("d{}{}", color.to_ascii_lowercase(), shape).parse()
+        }
+        _ => cell.parse(),
+    }
+}
 
-        let mut board_base = parse_meta(meta_data).map_err(|e| BadMetaData(e))?;
-        let mut row = 14;
-        // There is a lot of error handling obscuring the fact that this is actually really simple
-        // We keep track of where we are, starting at (14,0) and move to the right as we fill in cells. Finishing a row decreases our row by 1 and resets our column.
-        // Cells can be either a number that shifts us thta much to the right or a Piece which we put on the Board and shift by 1.
-        for line in board.split("/") {
+impl Board {
+    /// Reads the grid drawn by [`Board::to_ascii`], so debug dumps and diagrams in tests can be
+    /// turned back into boards.
+    ///
+    /// Blank lines and extra whitespace between squares are ignored. There must be 14 rows of 14
+    /// squares each, and the errors use the same row and column numbering as [`FromStr`]. The
+    /// metadata is the same as [`Board::empty`].
+    ///
+    /// ```
+    /// # use fen4::Board;
+    /// let board = Board::from_ascii(&Board::default().to_ascii()).unwrap();
+    /// assert_eq!(board, Board::default());
+    /// ```
+    pub fn from_ascii(diagram: &str) -> Result<Board, BoardParseError> {
+        use BoardParseError::*;
+        use BoardSize::*;
+        let mut board = Board::empty();
+        let mut row = BOARD_SIZE;
+        for line in diagram.lines().filter(|line| !line.trim().is_empty()) {
             if row == 0 {
                 return Err(BadBoardSize(TooManyRows, row));
             }
             row -= 1;
             let mut col = 0;
-            for segment in line.split(",") {
-                if col >= 14 {
+            for cell in line.split_whitespace() {
+                if col >= BOARD_SIZE {
                     return Err(BadBoardSize(TooManyColumns, row));
                 }
-                let trimmed = segment.trim();
-                if trimmed
-                    .chars()
-                    .next()
-                    .ok_or(EmptySegment(row, col))?
-                    .is_digit(10)
-                {
-                    let spaces = trimmed
-                        .parse::<usize>()
-                        .map_err(|e| BadSegmentNumber(row, col, e))?;
-                    col += spaces;
-                } else {
-                    board_base.board[row][col] = trimmed
-                        .parse::<Piece>()
-                        .map_err(|e| BadSegmentPiece(row, col, e))?;
-                    col += 1;
-                }
+                board.board[row][col] =
+                    parse_ascii_cell(cell).map_err(|e| BadSegmentPiece(row, col, e))?;
+                col += 1;
             }
-            if col != 14 {
+            if col != BOARD_SIZE {
                 return Err(BadBoardSize(TooFewColumns, row));
             }
         }
         if row != 0 {
             return Err(BadBoardSize(TooFewRows, row));
         }
-        Ok(board_base)
+        Ok(board)
+    }
+}
+
+fn first_error((board, errors): (Board, Vec<BoardParseError>)) -> Result<Board, BoardParseError> {
+    match errors.into_iter().next() {
+        Some(e) => Err(e),
+        None => Ok(board),
+    }
+}
+
+// Removes a leading "N:" row label, which can't be confused with a segment since pieces never contain ':'
+fn strip_row_index(line: &str) -> &str {
+    match line.find(':') {
+        Some(colon) if line[..colon].trim().parse::<usize>().is_ok() => {
+            line[colon + 1..].trim_start()
+        }
+        _ => line,
+    }
+}
+
+#[derive(Clone, Copy, Default)]
+struct ParseOptions {
+    // Accept the deviations described in Board::parse_lenient
+    lenient: bool,
+    // Replace pieces that fail to parse with '?' pieces, as in Board::from_fen_lossy
+    lossy: bool,
+}
+
+// The '?' piece for a segment that failed to parse, keeping its color if that part is readable
+fn placeholder_piece(segment: &str) -> Piece {
+    // The color is either one letter or 'd' and one letter, so try the longer prefix first
+    for len in &[2, 1] {
+        let prefix: String = segment.chars().take(*len).collect();
+        if let Ok(Piece::Normal(color, _)) = format!("{}?", prefix).parse() {
+            return Piece::Normal(color, '?');
+        }
+    }
+    Piece::Normal(Color::Dead(None), '?')
+}
+
+// Parses the whole fen4, recording every problem rather than returning early
+fn parse_board(fen: &str, options: ParseOptions) -> (Board, Vec<BoardParseError>) {
+    use BoardParseError::*;
+    use BoardSize::*;
+    let mut errors = Vec::new();
+    let fen = fen.trim_matches(|c: char| c.is_ascii_whitespace());
+    let (meta_data, board) = if let Some(last_dash) = fen.rfind('-') {
+        (Some(&fen[..last_dash]), &fen[last_dash + 1..])
+    } else {
+        errors.push(NoDash);
+        (None, fen)
+    };
+
+    let mut board_base = match meta_data.map(parse_meta) {
+        Some(Ok(base)) => base,
+        Some(Err(e)) => {
+            errors.push(BadMetaData(e));
+            Board::empty()
+        }
+        None => Board::empty(),
+    };
+    let mut row = BOARD_SIZE;
+    // There is a lot of error handling obscuring the fact that this is actually really simple
+    // We keep track of where we are, starting at (BOARD_SIZE,0) and move to the right as we fill in cells. Finishing a row decreases our row by 1 and resets our column.
+    // Cells can be either a number that shifts us thta much to the right or a Piece which we put on the Board and shift by 1.
+    let lines: Vec<&str> = if options.lenient && !board.contains('/') {
+        board.trim().lines().collect()
+    } else {
+        board.split('/').collect()
+    };
+    for line in lines {
+        let mut line = line.trim_matches(|c: char| c.is_ascii_whitespace());
+        if options.lenient {
+            line = strip_row_index(line);
+        }
+        if row == 0 {
+            errors.push(BadBoardSize(TooManyRows, row));
+            break;
+        }
+        row -= 1;
+        let mut col = 0;
+        let mut overflowed = false;
+        for (index, segment) in line.split(',').enumerate() {
+            if col >= BOARD_SIZE {
+                errors.push(ColumnOverflow(row, index));
+                overflowed = true;
+                break;
+            }
+            let trimmed = segment.trim();
+            match trimmed.chars().next() {
+                None if options.lenient => {}
+                None => errors.push(EmptySegment(row, col)),
+                Some(c) if c.is_ascii_digit() => match trimmed.parse::<usize>() {
+                    Ok(spaces) if col + spaces > BOARD_SIZE => {
+                        errors.push(ColumnOverflow(row, index));
+                        overflowed = true;
+                        break;
+                    }
+                    Ok(spaces) => col += spaces,
+                    Err(e) => errors.push(BadSegmentNumber(row, col, e)),
+                },
+                Some(_) => {
+                    match trimmed.parse::<Piece>() {
+                        Ok(piece) => board_base.board[row][col] = piece,
+                        Err(e) => {
+                            if options.lossy {
+                                board_base.board[row][col] = placeholder_piece(trimmed);
+                            }
+                            errors.push(BadSegmentPiece(row, col, e))
+                        }
+                    }
+                    col += 1;
+                }
+            }
+        }
+        if col != BOARD_SIZE && !overflowed {
+            errors.push(BadBoardSize(TooFewColumns, row));
+        }
+    }
+    if row != 0 {
+        errors.push(BadBoardSize(TooFewRows, row));
     }
+    (board_base, errors)
 }
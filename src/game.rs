@@ -0,0 +1,209 @@
+use crate::types::*;
+
+/// Points [`Board::apply_checkmate`] awards in Free-for-all for checkmating another player
+pub const CHECKMATE_POINTS: u16 = 20;
+
+impl Board {
+    /// Applies a player resigning.
+    ///
+    /// The `resigned` flag is always set. What else happens depends on [`Board::game_mode`]:
+    ///   - In Free-for-all (or when the mode is [`GameMode::Unknown`]) the player is marked `dead`, all
+    ///     of their pieces become dead pieces that remember their color, and if it was their turn
+    ///     the turn passes to the next living player.
+    ///   - In Teams a resignation ends the game for the whole team, so nothing else on the board changes.
+    pub fn resign(&mut self, color: TurnColor) {
+        self.extra_options.resigned[usize::from(color)] = true;
+        if self.game_mode() != GameMode::Teams {
+            self.eliminate(color);
+        }
+    }
+
+    /// Applies a player running out of time.
+    ///
+    /// This sets the `flagged` flag and otherwise behaves exactly like [`Board::resign`]: in Free-for-all
+    /// the player is eliminated and their pieces are greyed out, in Teams the game is over for the team.
+    ///
+    /// With "DeadKingWalking" chess.com keeps moving the king of a flagged or resigned player. That
+    /// setting is not stored in the fen4, so the king is greyed out here like every other piece.
+    pub fn flag(&mut self, color: TurnColor) {
+        self.extra_options.flagged[usize::from(color)] = true;
+        if self.game_mode() != GameMode::Teams {
+            self.eliminate(color);
+        }
+    }
+
+    /// Applies `by` checkmating `mated` in Free-for-all.
+    ///
+    /// `by` is awarded [`CHECKMATE_POINTS`] (20, as on chess.com) and `mated` is eliminated like a
+    /// resigned player: they are marked `dead`, their pieces become dead pieces that remember their
+    /// color, and if it was their turn the turn passes to the next living player. Points already
+    /// scored by `mated` are left alone. Games configured with a different bonus can use
+    /// [`Board::apply_checkmate_with`].
+    ///
+    /// ```
+    /// # use fen4::{Board, TurnColor};
+    /// let mut board = Board::default();
+    /// board.apply_checkmate(TurnColor::Blue, TurnColor::Red);
+    /// assert_eq!(board.points, [20, 0, 0, 0]);
+    /// assert!(board.dead[1]);
+    /// ```
+    pub fn apply_checkmate(&mut self, mated: TurnColor, by: TurnColor) {
+        self.apply_checkmate_with(mated, by, CHECKMATE_POINTS);
+    }
+
+    /// Applies `by` checkmating `mated` like [`Board::apply_checkmate`], awarding `bonus` points
+    /// instead of [`CHECKMATE_POINTS`].
+    ///
+    /// The points saturate at `u16::MAX`. A bonus of 0 only eliminates `mated`.
+    ///
+    /// ```
+    /// # use fen4::{Board, TurnColor};
+    /// let mut board = Board::default();
+    /// board.apply_checkmate_with(TurnColor::Blue, TurnColor::Red, 30);
+    /// assert_eq!(board.points, [30, 0, 0, 0]);
+    /// assert!(board.dead[1]);
+    /// ```
+    pub fn apply_checkmate_with(&mut self, mated: TurnColor, by: TurnColor, bonus: u16) {
+        let points = &mut self.points[usize::from(by)];
+        *points = points.saturating_add(bonus);
+        self.eliminate(mated);
+    }
+
+    /// Squares of the pieces that count as `color`'s king for checks and checkmate.
+    ///
+    /// If the `royal` tag names squares for `color`, the pieces on those squares that are still
+    /// theirs are the royal pieces, and they don't have to be kings. Otherwise every living king of
+    /// that color is royal. Either way a player can have several royals.
+    ///
+    /// Every royal has to be kept safe: [`Board::is_in_check`] counts a player as in check when any
+    /// one of them is attacked, and [`Board::legal_moves`] only allows moves that leave all of them
+    /// unattacked. A royal that has been captured no longer counts.
+    ///
+    /// ```
+    /// # use fen4::{Board, Position, TurnColor};
+    /// let board = Board::default();
+    /// assert_eq!(board.royal_squares(TurnColor::Red), vec![Position { row: 0, col: 7 }]);
+    /// ```
+    pub fn royal_squares(&self, color: TurnColor) -> Vec<Position> {
+        let living = Color::Turn(color);
+        let tagged = &self.extra_options.royal[usize::from(color)];
+        if tagged.is_empty() {
+            return self.find(&Piece::Normal(living, 'K'));
+        }
+        tagged
+            .iter()
+            .copied()
+            .filter(|pos| {
+                let piece = self.board.get(pos.row).and_then(|row| row.get(pos.col));
+                piece.and_then(Piece::color) == Some(living)
+            })
+            .collect()
+    }
+
+    /// Number of royal pieces `color` has, see [`Board::royal_squares`].
+    pub fn count_royals(&self, color: TurnColor) -> usize {
+        self.royal_squares(color).len()
+    }
+
+    /// Sum of every player's points.
+    pub fn total_points(&self) -> u32 {
+        self.points.iter().map(|&p| u32::from(p)).sum()
+    }
+
+    /// Sum of the points of both players on `team`.
+    ///
+    /// ```
+    /// # use fen4::{Board, Team};
+    /// let board = Board { points: [10, 25, 3, 0], ..Board::default() };
+    /// assert_eq!(board.total_points(), 38);
+    /// assert_eq!(board.team_points(Team::RedYellow), 13);
+    /// assert_eq!(board.team_points(Team::BlueGreen), 25);
+    /// ```
+    pub fn team_points(&self, team: Team) -> u32 {
+        TurnColor::iter()
+            .filter(|color| color.team() == team)
+            .map(|color| u32::from(self.points[usize::from(color)]))
+            .sum()
+    }
+
+    /// Each player's color, points, and whether they are still alive, highest points first.
+    ///
+    /// Players with the same points stay in turn order.
+    ///
+    /// ```
+    /// # use fen4::{Board, TurnColor};
+    /// let board = Board { points: [10, 25, 10, 0], ..Board::default() };
+    /// assert_eq!(board.standings()[0], (TurnColor::Blue, 25, true));
+    /// assert_eq!(board.standings()[1], (TurnColor::Red, 10, true));
+    /// ```
+    pub fn standings(&self) -> Vec<(TurnColor, u16, bool)> {
+        let mut standings: Vec<_> = TurnColor::iter()
+            .map(|color| {
+                let index = usize::from(color);
+                (color, self.points[index], !self.dead[index])
+            })
+            .collect();
+        // The sort is stable so ties keep turn order
+        standings.sort_by_key(|&(_, points, _)| std::cmp::Reverse(points));
+        standings
+    }
+
+    /// Marks `color` dead and turns each of their pieces into a dead piece that remembers its color.
+    ///
+    /// This is how chess.com shows an eliminated player in Free-for-all. Unlike [`Board::resign`] and
+    /// friends, nothing else changes: the turn stays where it is and no tags are set.
+    ///
+    /// ```
+    /// # use fen4::{Board, Color, Piece, TurnColor};
+    /// let mut board = Board::default();
+    /// board.kill_player(TurnColor::Yellow);
+    /// assert_eq!(board.dead, [false, false, true, false]);
+    /// assert_eq!(board.board[13][6], Piece::Normal(Color::Dead(Some(TurnColor::Yellow)), 'K'));
+    /// ```
+    pub fn kill_player(&mut self, color: TurnColor) {
+        self.dead[usize::from(color)] = true;
+        for piece in self.board.iter_mut().flatten() {
+            if let Some(c) = piece.color_mut() {
+                if *c == Color::Turn(color) {
+                    *c = Color::Dead(Some(color));
+                }
+            }
+        }
+    }
+
+    /// The inverse of [`Board::kill_player`]: marks `color` alive and turns the dead pieces that
+    /// remember being `color`'s back into living pieces.
+    ///
+    /// Dead pieces without a color (`Color::Dead(None)`) are left alone since there is no way to tell
+    /// whose they were. Tags like `resigned` and `flagged` are not touched either.
+    ///
+    /// ```
+    /// # use fen4::{Board, TurnColor};
+    /// let mut board = Board::default();
+    /// board.kill_player(TurnColor::Yellow);
+    /// board.revive_player(TurnColor::Yellow);
+    /// assert_eq!(board, Board::default());
+    /// ```
+    pub fn revive_player(&mut self, color: TurnColor) {
+        self.dead[usize::from(color)] = false;
+        for piece in self.board.iter_mut().flatten() {
+            if let Some(c) = piece.color_mut() {
+                if *c == Color::Dead(Some(color)) {
+                    *c = Color::Turn(color);
+                }
+            }
+        }
+    }
+
+    // Kills a player in Free-for-all and moves the turn past them
+    fn eliminate(&mut self, color: TurnColor) {
+        self.kill_player(color);
+        if self.turn == color {
+            let mut next = color.next();
+            while self.dead[usize::from(next)] && next != color {
+                next = next.next();
+            }
+            self.turn = next;
+        }
+    }
+}
@@ -0,0 +1,194 @@
+use crate::types::*;
+
+impl Position {
+    /// Makes a position from a column and row, both counted from 0, in that order like "a4".
+    ///
+    /// Nothing is checked so this can be used for constants.
+    ///
+    /// ```
+    /// # use fen4::Position;
+    /// const RED_KING_START: Position = Position::from_coords(7, 0);
+    /// assert_eq!(RED_KING_START.to_string(), "h1");
+    /// ```
+    pub const fn from_coords(col: usize, row: usize) -> Position {
+        Position { row, col }
+    }
+
+    /// The letter of the position's file, `'a'` for column 0 through `'n'` for column 13.
+    ///
+    /// # Panics
+    /// Panics if the column is not on the board.
+    ///
+    /// ```
+    /// # use fen4::Position;
+    /// assert_eq!(Position { row: 3, col: 0 }.column_char(), 'a');
+    /// assert_eq!(Position { row: 3, col: 13 }.column_char(), 'n');
+    /// ```
+    pub fn column_char(&self) -> char {
+        assert!(
+            self.col < BOARD_SIZE,
+            "column {} is off the board",
+            self.col
+        );
+        char::from(b'a' + self.col as u8)
+    }
+
+    /// The rank number of the position, `1` for row 0 through `14` for row 13.
+    ///
+    /// ```
+    /// # use fen4::Position;
+    /// assert_eq!(Position { row: 0, col: 3 }.row_number(), 1);
+    /// ```
+    pub fn row_number(&self) -> usize {
+        self.row + 1
+    }
+
+    /// Rotates the position counter-clockwise around the center of the board by 90 degrees `quarter_turns` times.
+    ///
+    /// One quarter turn moves the left edge (Blue's side) to the bottom.
+    ///
+    /// ```
+    /// # use fen4::Position;
+    /// let a4 = Position { row: 3, col: 0 };
+    /// assert_eq!(a4.rotated(1), Position { row: 0, col: 10 });
+    /// assert_eq!(a4.rotated(4), a4);
+    /// ```
+    pub fn rotated(&self, quarter_turns: u8) -> Position {
+        let mut pos = *self;
+        for _ in 0..quarter_turns % 4 {
+            pos = Position {
+                row: pos.col,
+                col: BOARD_SIZE - 1 - pos.row,
+            };
+        }
+        pos
+    }
+
+    /// Whether the position is on the cross shaped playing area rather than in one of the 3x3 corners.
+    ///
+    /// The fen4 usually leaves the corners empty instead of filling them with walls, so this is the
+    /// only reliable way to tell them apart from empty squares.
+    ///
+    /// ```
+    /// # use fen4::Position;
+    /// assert!(!Position { row: 2, col: 2 }.is_playable());
+    /// assert!(Position { row: 2, col: 3 }.is_playable());
+    /// ```
+    pub fn is_playable(&self) -> bool {
+        let edge = |i: usize| !(3..=10).contains(&i);
+        self.row < BOARD_SIZE && self.col < BOARD_SIZE && !(edge(self.row) && edge(self.col))
+    }
+
+    /// Number of king moves between two positions, ignoring anything in the way.
+    ///
+    /// ```
+    /// # use fen4::Position;
+    /// let a1 = Position { row: 0, col: 0 };
+    /// assert_eq!(a1.chebyshev_distance(&Position { row: 13, col: 13 }), 13);
+    /// ```
+    pub fn chebyshev_distance(&self, other: &Position) -> usize {
+        let rows = (self.row as isize - other.row as isize).abs();
+        let cols = (self.col as isize - other.col as isize).abs();
+        rows.max(cols) as usize
+    }
+
+    /// Number of rook steps between two positions, ignoring anything in the way.
+    ///
+    /// ```
+    /// # use fen4::Position;
+    /// let a1 = Position { row: 0, col: 0 };
+    /// assert_eq!(a1.manhattan_distance(&Position { row: 13, col: 13 }), 26);
+    /// ```
+    pub fn manhattan_distance(&self, other: &Position) -> usize {
+        let rows = (self.row as isize - other.row as isize).abs();
+        let cols = (self.col as isize - other.col as isize).abs();
+        (rows + cols) as usize
+    }
+}
+
+impl Board {
+    /// Every playable position (see [`Position::is_playable`]) ordered by row then column.
+    ///
+    /// ```
+    /// # use fen4::Board;
+    /// assert_eq!(Board::playable_positions().count(), 14 * 14 - 4 * 9);
+    /// ```
+    pub fn playable_positions() -> impl Iterator<Item = Position> {
+        (0..BOARD_SIZE)
+            .flat_map(|row| (0..BOARD_SIZE).map(move |col| Position { row, col }))
+            .filter(Position::is_playable)
+    }
+
+    /// Rotates the board counter-clockwise by 90 degrees `quarter_turns` times.
+    ///
+    /// This is meant for viewing the board from another player's side: `rotated(1)` puts Blue at
+    /// the bottom, `rotated(2)` Yellow and `rotated(3)` Green. Every square moves as described in
+    /// [`Position::rotated`].
+    ///
+    /// Pieces keep their colors, so `turn` and the per-player arrays (`dead`, `castling_king`,
+    /// `castling_queen`, `points`, and the arrays in [`Extra`]) still refer to the same players and are
+    /// unchanged. Only data that names squares moves with the board: each `royal` square and both
+    /// squares of each `enpassant` pair are rotated exactly like the pieces on them.
+    ///
+    /// The result still describes the same game, but chess.com always puts Red at the bottom, so
+    /// rotated boards are mostly useful for display.
+    pub fn rotated(&self, quarter_turns: u8) -> Board {
+        let mut output = self.clone();
+        for (row, pieces) in self.board.iter().enumerate() {
+            for (col, piece) in pieces.iter().enumerate() {
+                let to = Position { row, col }.rotated(quarter_turns);
+                output.board[to.row][to.col] = piece.clone();
+            }
+        }
+        let extra = &mut output.extra_options;
+        for royal in extra.royal.iter_mut().flatten() {
+            *royal = royal.rotated(quarter_turns);
+        }
+        for (capture, passed) in extra.enpassant.iter_mut().flatten() {
+            *capture = capture.rotated(quarter_turns);
+            *passed = passed.rotated(quarter_turns);
+        }
+        output
+    }
+
+    /// Rows of the board from top to bottom as seen by `viewer`, each paired with its label.
+    ///
+    /// The board is turned with [`Board::rotated`] so `viewer` sits at the bottom. Red and Yellow see
+    /// the ranks as rows, so their labels are the rank numbers ("14" down to "1" for Red). Blue and
+    /// Green see the files as rows, so their labels are the file letters ("n" down to "a" for Blue).
+    pub fn display_rows(&self, viewer: TurnColor) -> Vec<(String, Vec<Piece>)> {
+        let quarter_turns = usize::from(viewer) as u8;
+        let rotated = self.rotated(quarter_turns);
+        (0..BOARD_SIZE)
+            .rev()
+            .map(|row| {
+                let origin = Position { row, col: 0 }.rotated(4 - quarter_turns);
+                let label = match viewer {
+                    TurnColor::Red | TurnColor::Yellow => origin.row_number().to_string(),
+                    TurnColor::Blue | TurnColor::Green => origin.column_char().to_string(),
+                };
+                (label, rotated.board[row].to_vec())
+            })
+            .collect()
+    }
+
+    /// The squares of a two player (`std2pc`) game as seen by `viewer`, with `viewer` at the bottom.
+    ///
+    /// This is the two player counterpart to [`Board::rotated`]: Red sees the board as it is stored
+    /// and Yellow sees it turned halfway around. Returns `None` if the board is not marked `std2pc`
+    /// or `viewer` is not one of the two players (Red and Yellow).
+    pub fn two_player_perspective(
+        &self,
+        viewer: TurnColor,
+    ) -> Option<[[Piece; BOARD_SIZE]; BOARD_SIZE]> {
+        if !self.extra_options.std2pc {
+            return None;
+        }
+        match viewer {
+            TurnColor::Red | TurnColor::Yellow => {
+                Some(self.rotated(usize::from(viewer) as u8).board)
+            }
+            TurnColor::Blue | TurnColor::Green => None,
+        }
+    }
+}
@@ -13,13 +13,47 @@
 //! # Ok(())
 //! # }
 //! ```
+//!
+//! ### Features
+//!
+//! - `serde`: implements `Serialize` and `Deserialize` for the public types. [`Board`] is (de)serialized as its fen4 string.
+//! - `multi-char-fairy`: adds [`Piece::Fairy`] for pieces with shapes longer than one character, like `rAB`, which otherwise fail to parse.
 
+mod binary;
+mod builder;
+mod cache;
 mod conversions;
+mod delta;
 mod display;
 mod from_str;
+mod game;
+mod geometry;
+mod moves;
+mod pgn4;
+#[cfg(feature = "serde")]
+mod serde_impl;
 mod types;
+mod validate;
+mod zobrist;
 
+pub use builder::BoardBuilder;
+pub use cache::MoveCache;
+pub use conversions::ConversionError;
+pub use delta::BoardDelta;
+pub use delta::DeltaParseError;
+pub use from_str::parse_points;
+pub use from_str::parse_position_pair;
+pub use from_str::parse_turn;
 pub use from_str::BoardParseError;
+pub use from_str::BoardSize;
+pub use from_str::MetaDataParseError;
 pub use from_str::PieceParseError;
 pub use from_str::PositionParseError;
+pub use game::CHECKMATE_POINTS;
+pub use moves::Move;
+pub use moves::MoveError;
+pub use moves::MoveReport;
+pub use pgn4::replay_pgn4;
+pub use pgn4::Pgn4Error;
 pub use types::*;
+pub use validate::BoardError;
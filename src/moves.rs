@@ -0,0 +1,813 @@
+use std::ops::ControlFlow;
+
+use thiserror::Error;
+
+use crate::types::*;
+
+/// A piece moving from one square to another
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Move {
+    pub from: Position,
+    pub to: Position,
+}
+
+/// What happened when a move was applied with [`Board::apply_move_reporting`]
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct MoveReport {
+    /// The piece that was on the destination square, `Piece::Empty` if nothing was captured
+    pub captured: Piece,
+    /// Whether the moved piece was a pawn that was promoted to a queen
+    pub promoted: bool,
+    /// Which living players are in check after the move, indexed by `usize::from(color)`
+    pub checks: [bool; 4],
+}
+
+/// Enum to store all ways a move can fail to apply to a [`Board`]
+#[derive(Error, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MoveError {
+    #[error("There is no piece on the square being moved from")]
+    EmptySource,
+    #[error("{0:?} is not on the board")]
+    OffBoard(Position),
+    #[error("The piece on {0:?} is not a pawn")]
+    NotAPawn(Position),
+    #[error("{0} may not castle on the {1:?} side")]
+    NoCastlingRights(TurnColor, CastleSide),
+    #[error("{0} can't castle because a square between the king and rook is occupied")]
+    CastlingBlocked(TurnColor),
+}
+
+fn on_board(pos: Position) -> Result<Position, MoveError> {
+    if pos.row < BOARD_SIZE && pos.col < BOARD_SIZE {
+        Ok(pos)
+    } else {
+        Err(MoveError::OffBoard(pos))
+    }
+}
+
+const ORTHOGONAL: [(isize, isize); 4] = [(1, 0), (-1, 0), (0, 1), (0, -1)];
+const DIAGONAL: [(isize, isize); 4] = [(1, 1), (1, -1), (-1, 1), (-1, -1)];
+const KNIGHT_JUMPS: [(isize, isize); 8] = [
+    (1, 2),
+    (2, 1),
+    (2, -1),
+    (1, -2),
+    (-1, -2),
+    (-2, -1),
+    (-2, 1),
+    (-1, 2),
+];
+const KING_STEPS: [(isize, isize); 8] = [
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+];
+
+fn offset(pos: Position, (dr, dc): (isize, isize)) -> Option<Position> {
+    let row = pos.row as isize + dr;
+    let col = pos.col as isize + dc;
+    if (0..BOARD_SIZE as isize).contains(&row) && (0..BOARD_SIZE as isize).contains(&col) {
+        let to = Position {
+            row: row as usize,
+            col: col as usize,
+        };
+        if to.is_playable() {
+            return Some(to);
+        }
+    }
+    None
+}
+
+// Where `color`'s king, king side rook, and queen side rook start in the default position
+pub(crate) fn castling_homes(color: TurnColor) -> [Position; 3] {
+    let (king, king_rook, queen_rook) = match color {
+        TurnColor::Red => ((0, 7), (0, 10), (0, 3)),
+        TurnColor::Blue => ((7, 0), (10, 0), (3, 0)),
+        TurnColor::Yellow => ((13, 6), (13, 3), (13, 10)),
+        TurnColor::Green => ((6, 13), (3, 13), (10, 13)),
+    };
+    [king.into(), king_rook.into(), queen_rook.into()]
+}
+
+// The two diagonal steps a pawn of `color` captures with
+fn pawn_captures(color: TurnColor) -> [(isize, isize); 2] {
+    match color.forward() {
+        (0, dc) => [(1, dc), (-1, dc)],
+        (dr, _) => [(dr, 1), (dr, -1)],
+    }
+}
+
+// Whether the living piece on `from` attacks `target`, reading the squares through `at`. Only the
+// standard pieces attack anything.
+fn attacks_on<'a>(at: impl Fn(Position) -> &'a Piece, from: Position, target: Position) -> bool {
+    let (color, shape) = match at(from) {
+        Piece::Normal(Color::Turn(color), shape) => (*color, *shape),
+        _ => return false,
+    };
+    let reaches = |offsets: &[(isize, isize)]| {
+        offsets
+            .iter()
+            .any(|&direction| offset(from, direction) == Some(target))
+    };
+    let slides = |directions: &[(isize, isize)]| {
+        directions.iter().any(|&direction| {
+            let mut current = from;
+            while let Some(to) = offset(current, direction) {
+                if to == target {
+                    return true;
+                }
+                if !at(to).is_empty() {
+                    break;
+                }
+                current = to;
+            }
+            false
+        })
+    };
+    match shape {
+        'P' => reaches(&pawn_captures(color)),
+        'N' => reaches(&KNIGHT_JUMPS),
+        'K' => reaches(&KING_STEPS),
+        'R' => slides(&ORTHOGONAL),
+        'B' => slides(&DIAGONAL),
+        'Q' => slides(&ORTHOGONAL) || slides(&DIAGONAL),
+        _ => false,
+    }
+}
+
+// A visitor that pushes every square onto `out`
+fn push_to(out: &mut Vec<Position>) -> impl FnMut(Position) -> ControlFlow<()> + '_ {
+    move |to| {
+        out.push(to);
+        ControlFlow::Continue(())
+    }
+}
+
+// Whether a pawn of `color` promotes on `pos`, which is the 8th rank from that player's side
+fn promotes_on(color: TurnColor, pos: Position) -> bool {
+    match color {
+        TurnColor::Red => pos.row == 7,
+        TurnColor::Blue => pos.col == 7,
+        TurnColor::Yellow => pos.row == 6,
+        TurnColor::Green => pos.col == 6,
+    }
+}
+
+impl Board {
+    // The color of the living piece on `pos`, if there is one
+    fn mover(&self, pos: Position) -> Option<TurnColor> {
+        match self.board.get(pos.row)?.get(pos.col)? {
+            Piece::Normal(Color::Turn(color), _) => Some(*color),
+            _ => None,
+        }
+    }
+
+    // The piece that would be on `pos` after `mv`, without making the move
+    fn piece_after(&self, mv: Move, pos: Position) -> &Piece {
+        static EMPTY: Piece = Piece::Empty;
+        if pos == mv.from {
+            &EMPTY
+        } else if pos == mv.to {
+            &self.board[mv.from.row][mv.from.col]
+        } else {
+            &self.board[pos.row][pos.col]
+        }
+    }
+
+    // Slides from `from` in each direction until something is in the way, including captures
+    fn visit_slides<B>(
+        &self,
+        color: TurnColor,
+        from: Position,
+        directions: &[(isize, isize)],
+        visit: &mut impl FnMut(Position) -> ControlFlow<B>,
+    ) -> ControlFlow<B> {
+        for &direction in directions {
+            let mut current = from;
+            while let Some(to) = offset(current, direction) {
+                match &self.board[to.row][to.col] {
+                    Piece::Empty => visit(to)?,
+                    Piece::Wall => break,
+                    piece => {
+                        if piece.color() != Some(Color::Turn(color)) {
+                            visit(to)?;
+                        }
+                        break;
+                    }
+                }
+                current = to;
+            }
+        }
+        ControlFlow::Continue(())
+    }
+
+    // Squares one step away from `from` in each direction that are not walls or friendly pieces
+    fn visit_steps<B>(
+        &self,
+        color: TurnColor,
+        from: Position,
+        offsets: &[(isize, isize)],
+        visit: &mut impl FnMut(Position) -> ControlFlow<B>,
+    ) -> ControlFlow<B> {
+        for &direction in offsets {
+            if let Some(to) = offset(from, direction) {
+                let open = match &self.board[to.row][to.col] {
+                    Piece::Empty => true,
+                    Piece::Wall => false,
+                    piece => piece.color() != Some(Color::Turn(color)),
+                };
+                if open {
+                    visit(to)?;
+                }
+            }
+        }
+        ControlFlow::Continue(())
+    }
+
+    // Pawn moves for the living pawn on `from`: one step forward, two from the `pawnsBaseRank` rank,
+    // and diagonal captures
+    fn visit_pawn_moves<B>(
+        &self,
+        color: TurnColor,
+        from: Position,
+        visit: &mut impl FnMut(Position) -> ControlFlow<B>,
+    ) -> ControlFlow<B> {
+        let forward = color.forward();
+        if let Some(one) = offset(from, forward).filter(|to| self.board[to.row][to.col].is_empty())
+        {
+            visit(one)?;
+            // The base rank is counted from the player's own side, and 0 means pawns never jump
+            let base = self.extra_options.pawnbaserank;
+            let on_start = base != 0
+                && match color.home_edge() {
+                    Edge::Bottom => from.row + 1 == base,
+                    Edge::Left => from.col + 1 == base,
+                    Edge::Top => BOARD_SIZE - from.row == base,
+                    Edge::Right => BOARD_SIZE - from.col == base,
+                };
+            if on_start {
+                if let Some(two) =
+                    offset(one, forward).filter(|to| self.board[to.row][to.col].is_empty())
+                {
+                    visit(two)?;
+                }
+            }
+        }
+        for &capture in &pawn_captures(color) {
+            if let Some(to) = offset(from, capture) {
+                if let Some(c) = self.board[to.row][to.col].color() {
+                    if c != Color::Turn(color) {
+                        visit(to)?;
+                    }
+                }
+            }
+        }
+        ControlFlow::Continue(())
+    }
+
+    // Every square the living piece of `color` with `shape` on `from` could move to, ignoring checks
+    fn visit_targets<B>(
+        &self,
+        color: TurnColor,
+        shape: char,
+        from: Position,
+        visit: &mut impl FnMut(Position) -> ControlFlow<B>,
+    ) -> ControlFlow<B> {
+        match shape {
+            'P' => self.visit_pawn_moves(color, from, visit),
+            'N' => self.visit_steps(color, from, &KNIGHT_JUMPS, visit),
+            'K' => self.visit_steps(color, from, &KING_STEPS, visit),
+            'R' => self.visit_slides(color, from, &ORTHOGONAL, visit),
+            'B' => self.visit_slides(color, from, &DIAGONAL, visit),
+            'Q' => {
+                self.visit_slides(color, from, &ORTHOGONAL, visit)?;
+                self.visit_slides(color, from, &DIAGONAL, visit)
+            }
+            _ => ControlFlow::Continue(()),
+        }
+    }
+
+    // Whether the living piece on `from` attacks `target`
+    fn attacks(&self, from: Position, target: Position) -> bool {
+        attacks_on(|pos| &self.board[pos.row][pos.col], from, target)
+    }
+
+    /// Whether any royal piece of `color` is attacked by a living piece of another color.
+    ///
+    /// The royal pieces are the ones from [`Board::royal_squares`]: the `royal` square if it is set,
+    /// otherwise every one of `color`'s kings. Dead pieces never attack, and teammates in Teams
+    /// count as other colors. A piece attacks a square when it could capture there:
+    ///   - Pawns capture one step diagonally forward, where forward is [`TurnColor::forward`] for
+    ///     their own color. Red pawns attack up-left and up-right (row + 1), Yellow pawns down-left
+    ///     and down-right (row - 1), Blue pawns up-right and down-right (column + 1) and Green pawns
+    ///     up-left and down-left (column - 1). Pushing straight forward is never an attack.
+    ///   - Knights and kings attack the squares they step to.
+    ///   - Rooks, bishops and queens attack along their lines up to and including the first square
+    ///     that isn't empty, so walls and pieces of any color block them.
+    ///   - Fairy pieces don't attack anything.
+    ///
+    /// ```
+    /// # use fen4::{Board, Color, Piece, Position, TurnColor};
+    /// let board = Board::default();
+    /// assert!(!board.is_in_check(TurnColor::Red));
+    /// let queen = Piece::Normal(Color::Turn(TurnColor::Blue), 'Q');
+    /// let board = board.with_piece(Position { row: 1, col: 7 }, Piece::Empty).with_piece(Position { row: 4, col: 7 }, queen);
+    /// assert!(board.is_in_check(TurnColor::Red));
+    /// ```
+    pub fn is_in_check(&self, color: TurnColor) -> bool {
+        let royals = self.royal_squares(color);
+        Board::playable_positions().any(|from| {
+            matches!(self.mover(from), Some(c) if c != color)
+                && royals.iter().any(|&royal| self.attacks(from, royal))
+        })
+    }
+
+    /// Whether the player whose turn it is is stalemated: they aren't in check but have no legal move.
+    ///
+    /// This uses [`Board::is_in_check`] and [`Board::for_each_legal_move`], so the same rules apply:
+    /// only the standard pieces move and castling and en passant aren't considered. Dead players
+    /// are never stalemated.
+    ///
+    /// ```
+    /// # use fen4::Board;
+    /// assert!(!Board::default().is_stalemate());
+    /// ```
+    pub fn is_stalemate(&self) -> bool {
+        let color = self.turn;
+        !self.dead[usize::from(color)]
+            && !self.is_in_check(color)
+            && self
+                .for_each_legal_move(color, |_| ControlFlow::Break(()))
+                .is_continue()
+    }
+
+    // Whether none of `royals` (`color`'s royal squares before the move) is attacked after `mv`.
+    // The board is only read, with `piece_after` standing in for the moved pieces.
+    fn leaves_royals_safe(&self, color: TurnColor, royals: &[Position], mv: Move) -> bool {
+        let at = |pos| self.piece_after(mv, pos);
+        Board::playable_positions().all(|from| match at(from) {
+            Piece::Normal(Color::Turn(c), _) if *c != color => royals.iter().all(|&royal| {
+                let royal = if royal == mv.from { mv.to } else { royal };
+                !attacks_on(at, from, royal)
+            }),
+            _ => true,
+        })
+    }
+
+    /// Calls `f` with every legal move `color` can make with their living pieces, stopping early as
+    /// soon as `f` returns [`ControlFlow::Break`].
+    ///
+    /// This is the move generator behind [`Board::legal_moves`], and generates the same moves in the
+    /// same order. It doesn't allocate per move or clone the board, so search code can call it for
+    /// every node. Returns the `Break` value if `f` stopped early and `Continue` otherwise.
+    ///
+    /// ```
+    /// # use fen4::{Board, Position, TurnColor};
+    /// use std::ops::ControlFlow;
+    /// // Find the first move of the knight on i1
+    /// let board = Board::default();
+    /// let found = board.for_each_legal_move(TurnColor::Red, |mv| {
+    ///     if mv.from == (Position { row: 0, col: 9 }) {
+    ///         ControlFlow::Break(mv.to)
+    ///     } else {
+    ///         ControlFlow::Continue(())
+    ///     }
+    /// });
+    /// assert_eq!(found, ControlFlow::Break(Position { row: 2, col: 10 }));
+    /// ```
+    pub fn for_each_legal_move<B>(
+        &self,
+        color: TurnColor,
+        mut f: impl FnMut(Move) -> ControlFlow<B>,
+    ) -> ControlFlow<B> {
+        let royals = self.royal_squares(color);
+        for from in Board::playable_positions() {
+            let shape = match &self.board[from.row][from.col] {
+                Piece::Normal(Color::Turn(c), shape) if *c == color => *shape,
+                _ => continue,
+            };
+            self.visit_targets(color, shape, from, &mut |to| {
+                let mv = Move { from, to };
+                if self.leaves_royals_safe(color, &royals, mv) {
+                    f(mv)
+                } else {
+                    ControlFlow::Continue(())
+                }
+            })?;
+        }
+        ControlFlow::Continue(())
+    }
+
+    /// Every legal move `color` can make with their living pieces.
+    ///
+    /// Moves are generated for the standard pieces only: rooks, knights, bishops, queens, kings, and
+    /// pawns (one step, diagonal captures, and two steps from the `pawnsBaseRank` rank counted from
+    /// the player's own side, which is rank 2 unless the tag says otherwise and never when it is 0). Castling, en
+    /// passant, and fairy pieces are not included. A move is legal if afterwards none of `color`'s
+    /// royal pieces (see [`Board::royal_squares`]) is attacked by a living piece of another color.
+    ///
+    /// Moves are ordered by the square moved from, row then column. This collects
+    /// [`Board::for_each_legal_move`] into a `Vec`.
+    ///
+    /// ```
+    /// # use fen4::{Board, TurnColor};
+    /// // 8 pawns with 2 moves each and 2 knights with 2 moves each
+    /// assert_eq!(Board::default().legal_moves(TurnColor::Red).len(), 20);
+    /// ```
+    pub fn legal_moves(&self, color: TurnColor) -> Vec<Move> {
+        let mut out = Vec::new();
+        let _ = self.for_each_legal_move(color, |mv| {
+            out.push(mv);
+            ControlFlow::<()>::Continue(())
+        });
+        out
+    }
+
+    /// Squares a rook on `from` could move to, ignoring checks.
+    ///
+    /// The rook slides in the four orthogonal directions and stops before walls, the board edge,
+    /// the corners, and pieces of its own color. Squares with enemy pieces, including dead pieces,
+    /// are included as captures. If `from` does not hold a living piece there are no moves.
+    ///
+    /// ```
+    /// # use fen4::{Board, Position};
+    /// // Rooks start boxed in
+    /// assert!(Board::default().rook_moves(Position { row: 0, col: 3 }).is_empty());
+    /// ```
+    pub fn rook_moves(&self, from: Position) -> Vec<Position> {
+        let mut out = Vec::new();
+        if let Some(color) = self.mover(from) {
+            let _ = self.visit_slides(color, from, &ORTHOGONAL, &mut push_to(&mut out));
+        }
+        out
+    }
+
+    /// Squares a knight on `from` could move to, ignoring checks.
+    ///
+    /// These are the eight L-shaped jumps that stay on the board, outside of the corners, and do not
+    /// land on a wall or a piece of the knight's own color. If `from` does not hold a living piece
+    /// there are no moves.
+    ///
+    /// ```
+    /// # use fen4::{Board, Position};
+    /// let moves = Board::default().knight_moves(Position { row: 0, col: 4 });
+    /// assert_eq!(moves.len(), 2);
+    /// ```
+    pub fn knight_moves(&self, from: Position) -> Vec<Position> {
+        let mut out = Vec::new();
+        if let Some(color) = self.mover(from) {
+            let _ = self.visit_steps(color, from, &KNIGHT_JUMPS, &mut push_to(&mut out));
+        }
+        out
+    }
+
+    /// King moves for `color` from `from`.
+    ///
+    /// The king steps one square in any direction, staying on the board and out of the corners, and
+    /// may not land on a wall or one of `color`'s own pieces. Castling is not included, and whether
+    /// the king would be moving into check is not considered.
+    ///
+    /// ```
+    /// # use fen4::{Board, Position, TurnColor};
+    /// let board = Board::default();
+    /// assert!(board.legal_king_moves(Position { row: 0, col: 7 }, TurnColor::Red).is_empty());
+    /// ```
+    pub fn legal_king_moves(&self, from: Position, color: TurnColor) -> Vec<Move> {
+        let mut out = Vec::new();
+        let _ = self.visit_steps(color, from, &KING_STEPS, &mut push_to(&mut out));
+        out.into_iter().map(|to| Move { from, to }).collect()
+    }
+
+    /// Whether a pawn of `color` on `pos` is unable to step forward.
+    ///
+    /// The square in front of it (see [`TurnColor::forward`]) is blocked if it holds any piece,
+    /// including dead pieces and walls, or if it is off the board or in a corner. Only the square
+    /// itself is looked at, so a blocked pawn may still be able to capture diagonally.
+    ///
+    /// ```
+    /// # use fen4::{Board, Position, TurnColor};
+    /// assert!(!Board::default().pawn_is_blocked(Position { row: 1, col: 3 }, TurnColor::Red));
+    /// ```
+    pub fn pawn_is_blocked(&self, pos: Position, color: TurnColor) -> bool {
+        match offset(pos, color.forward()) {
+            Some(to) => !self.board[to.row][to.col].is_empty(),
+            None => true,
+        }
+    }
+
+    /// Moves the piece on `from` to `to` and returns whatever was captured (`Piece::Empty` if nothing).
+    ///
+    /// The turn passes to [`TurnColor::next`] and `draw_ply` is reset on a capture or pawn move and
+    /// incremented otherwise. Castling, en passant, and promotion are not handled; neither is checking
+    /// that the move is legal.
+    ///
+    /// Dead pieces can be moved as well, which is how a dead player's moves are made in "DeadKingWalking"
+    /// and zombie games. The turn advances the same way whoever moved: it always passes to the next color
+    /// without skipping dead players, because in those modes dead players still take their turns. Moving
+    /// out of turn (for example a dead king when it isn't that color's turn) isn't rejected either.
+    ///
+    /// ```
+    /// # use fen4::{Board, Piece, Position, TurnColor};
+    /// let mut board = Board::default();
+    /// let captured = board.make_move(Position { row: 1, col: 7 }, Position { row: 3, col: 7 });
+    /// assert_eq!(captured, Ok(Piece::Empty));
+    /// assert_eq!(board.turn, TurnColor::Blue);
+    /// ```
+    pub fn make_move(&mut self, from: Position, to: Position) -> Result<Piece, MoveError> {
+        let from = on_board(from)?;
+        let to = on_board(to)?;
+        let moving = std::mem::take(&mut self.board[from.row][from.col]);
+        if !moving.is_piece() {
+            self.board[from.row][from.col] = moving;
+            return Err(MoveError::EmptySource);
+        }
+        let is_pawn = moving.kind() == Some(PieceKind::Pawn);
+        let captured = std::mem::replace(&mut self.board[to.row][to.col], moving);
+        if is_pawn || captured.is_piece() {
+            self.draw_ply = 0;
+        } else {
+            self.draw_ply += 1;
+        }
+        self.turn = self.turn.next();
+        Ok(captured)
+    }
+
+    /// Promotes the pawn on `at` to `to_shape`, keeping its color.
+    ///
+    /// Dead pawns keep their dead color too. Whether `at` is a square the pawn may promote on is not
+    /// checked. Fails with [`MoveError::EmptySource`] if `at` is empty and [`MoveError::NotAPawn`] if
+    /// it holds a wall or any other piece.
+    ///
+    /// ```
+    /// # use fen4::{Board, Color, MoveError, Piece, Position, TurnColor};
+    /// let mut board = Board::default();
+    /// board.promote(Position { row: 1, col: 3 }, 'Q').unwrap();
+    /// assert_eq!(board.board[1][3], Piece::Normal(Color::Turn(TurnColor::Red), 'Q'));
+    /// assert_eq!(board.promote(Position { row: 1, col: 3 }, 'Q'), Err(MoveError::NotAPawn(Position { row: 1, col: 3 })));
+    /// ```
+    pub fn promote(&mut self, at: Position, to_shape: char) -> Result<(), MoveError> {
+        let at = on_board(at)?;
+        match &mut self.board[at.row][at.col] {
+            Piece::Normal(_, shape) if *shape == 'P' => {
+                *shape = to_shape;
+                Ok(())
+            }
+            Piece::Empty => Err(MoveError::EmptySource),
+            _ => Err(MoveError::NotAPawn(at)),
+        }
+    }
+
+    /// Writes the move from `from` to `to` in SAN-style notation, like `Nh3`, `Qxk5` or `hxi3`.
+    ///
+    /// The piece letter is left out for pawns and `x` is added when `to` holds a piece. Fairy pieces
+    /// use their raw shape. A pawn capture starts with the line the pawn came from: the file for Red
+    /// and Yellow and the rank for Blue and Green, since those are the lines their captures leave.
+    ///
+    /// Other pieces get the usual disambiguation when another living piece of the same color and
+    /// shape could also reach `to`: the file if that tells them apart, otherwise the rank, otherwise
+    /// the whole square. Only the standard pieces are checked for this. The move itself is not
+    /// checked, it only fails with [`MoveError::OffBoard`] or with [`MoveError::EmptySource`] if
+    /// `from` doesn't hold a piece.
+    ///
+    /// ```
+    /// # use fen4::{Board, Position};
+    /// let board = Board::default();
+    /// assert_eq!(board.describe_move(Position { row: 0, col: 9 }, Position { row: 2, col: 8 }), Ok("Ni3".to_string()));
+    /// assert_eq!(board.describe_move(Position { row: 1, col: 7 }, Position { row: 3, col: 7 }), Ok("h4".to_string()));
+    /// ```
+    pub fn describe_move(&self, from: Position, to: Position) -> Result<String, MoveError> {
+        let from = on_board(from)?;
+        let to = on_board(to)?;
+        let moving = &self.board[from.row][from.col];
+        let (color, shape) = match (moving.color(), moving.shape_str()) {
+            (Some(color), Some(shape)) => (color, shape),
+            _ => return Err(MoveError::EmptySource),
+        };
+        let capture = self.board[to.row][to.col].is_piece();
+        let file = |pos: Position| pos.column_char().to_string();
+        let rank = |pos: Position| pos.row_number().to_string();
+
+        let mut out = String::new();
+        if shape == "P" {
+            if capture {
+                match color {
+                    Color::Turn(TurnColor::Blue) | Color::Turn(TurnColor::Green) => {
+                        out.push_str(&rank(from))
+                    }
+                    _ => out.push_str(&file(from)),
+                }
+            }
+        } else {
+            out.push_str(&shape);
+            let rivals: Vec<Position> = Board::playable_positions()
+                .filter(|&other| {
+                    other != from
+                        && self.board[other.row][other.col] == *moving
+                        && self.attacks(other, to)
+                })
+                .collect();
+            if !rivals.is_empty() {
+                if rivals.iter().all(|other| other.col != from.col) {
+                    out.push_str(&file(from));
+                } else if rivals.iter().all(|other| other.row != from.row) {
+                    out.push_str(&rank(from));
+                } else {
+                    out.push_str(&from.to_string());
+                }
+            }
+        }
+        if capture {
+            out.push('x');
+        }
+        out.push_str(&to.to_string());
+        Ok(out)
+    }
+
+    /// Clears the castling rights that a piece leaving `from` gives up.
+    ///
+    /// Leaving a king's starting square clears both of that player's flags and leaving a rook's
+    /// starting square clears the flag for that side. The starting squares are the same ones
+    /// [`Board::castle`] uses. Nothing changes if `from` isn't one of them. What is (or was) on
+    /// `from` isn't looked at, so this can be called before or after the move.
+    ///
+    /// ```
+    /// # use fen4::{Board, Position};
+    /// let mut board = Board::default();
+    /// board.update_castling_after_move(Position { row: 3, col: 0 });
+    /// assert_eq!(board.castling_queen, [true, false, true, true]);
+    /// assert_eq!(board.castling_king, [true; 4]);
+    /// ```
+    pub fn update_castling_after_move(&mut self, from: Position) {
+        for color in TurnColor::iter() {
+            let index = usize::from(color);
+            let [king, king_rook, queen_rook] = castling_homes(color);
+            if from == king || from == king_rook {
+                self.castling_king[index] = false;
+            }
+            if from == king || from == queen_rook {
+                self.castling_queen[index] = false;
+            }
+        }
+    }
+
+    /// Whether a pawn of `color` promotes when it reaches `pos`.
+    ///
+    /// chess.com promotes pawns on the 8th rank from the player's own side, which is the line just
+    /// past the middle of the board rather than the far edge:
+    ///   - Red on rank 8 (row 7)
+    ///   - Blue on the h-file (column 7)
+    ///   - Yellow on rank 7 (row 6)
+    ///   - Green on the g-file (column 6)
+    ///
+    /// Squares that aren't playable are never promotion squares.
+    ///
+    /// ```
+    /// # use fen4::{Board, Position, TurnColor};
+    /// let board = Board::default();
+    /// assert!(board.is_promotion_square(TurnColor::Red, Position { row: 7, col: 4 }));
+    /// assert!(!board.is_promotion_square(TurnColor::Yellow, Position { row: 7, col: 4 }));
+    /// ```
+    pub fn is_promotion_square(&self, color: TurnColor, pos: Position) -> bool {
+        pos.is_playable() && promotes_on(color, pos)
+    }
+
+    /// Records that `color`'s pawn just passed over `capture` and now stands on `passed`.
+    ///
+    /// Another player's pawn may capture on `capture`, which removes the pawn on `passed`. This is
+    /// the `enPassant` tag for `color`, replacing whatever was there.
+    ///
+    /// ```
+    /// # use fen4::{Board, Position, TurnColor};
+    /// let mut board = Board::default();
+    /// let (capture, passed) = (Position { row: 2, col: 9 }, Position { row: 3, col: 9 });
+    /// board.set_en_passant(TurnColor::Red, capture, passed);
+    /// assert_eq!(board.en_passant_for(TurnColor::Red), Some((capture, passed)));
+    /// assert!(board.to_string().contains("'enPassant':('j3:j4','','','')"));
+    /// ```
+    pub fn set_en_passant(&mut self, color: TurnColor, capture: Position, passed: Position) {
+        self.extra_options.enpassant[usize::from(color)] = Some((capture, passed));
+    }
+
+    /// Removes `color`'s en passant squares, see [`Board::set_en_passant`]
+    pub fn clear_en_passant(&mut self, color: TurnColor) {
+        self.extra_options.enpassant[usize::from(color)] = None;
+    }
+
+    /// The `(capture, passed)` squares set by [`Board::set_en_passant`] for `color`, if any
+    pub fn en_passant_for(&self, color: TurnColor) -> Option<(Position, Position)> {
+        self.extra_options.enpassant[usize::from(color)]
+    }
+
+    /// Castles `color`'s king with the rook on `side`, then passes the turn like [`Board::make_move`].
+    ///
+    /// The king moves two squares along its back rank towards the rook and the rook lands on the
+    /// square the king passed over. The back rank depends on the player: row 0 for Red, column 0 for
+    /// Blue, row 13 for Yellow and column 13 for Green. For example Red's king side castle moves the
+    /// king from h1 to j1 and the rook from k1 to i1. Both castling flags of `color` are cleared.
+    ///
+    /// Fails with [`MoveError::NoCastlingRights`] if the flag for `side` is not set,
+    /// [`MoveError::EmptySource`] if the king or rook is not on its starting square, and
+    /// [`MoveError::CastlingBlocked`] if a square between them is not empty. Whether the king is in
+    /// or passes through check is not checked.
+    ///
+    /// ```
+    /// # use fen4::{Board, CastleSide, Color, Piece, TurnColor};
+    /// let mut board = Board::default();
+    /// board.board[0][8] = Piece::Empty;
+    /// board.board[0][9] = Piece::Empty;
+    /// board.castle(TurnColor::Red, CastleSide::King).unwrap();
+    /// assert_eq!(board.board[0][9], Piece::Normal(Color::Turn(TurnColor::Red), 'K'));
+    /// assert_eq!(board.board[0][8], Piece::Normal(Color::Turn(TurnColor::Red), 'R'));
+    /// assert!(!board.castling_queen[0]);
+    /// ```
+    pub fn castle(&mut self, color: TurnColor, side: CastleSide) -> Result<(), MoveError> {
+        let index = usize::from(color);
+        let allowed = match side {
+            CastleSide::King => self.castling_king[index],
+            CastleSide::Queen => self.castling_queen[index],
+        };
+        if !allowed {
+            return Err(MoveError::NoCastlingRights(color, side));
+        }
+        let [king, king_rook, queen_rook] = castling_homes(color);
+        let rook = match side {
+            CastleSide::King => king_rook,
+            CastleSide::Queen => queen_rook,
+        };
+        let step = |from: usize, to: usize| (to as isize - from as isize).signum();
+        let direction = (step(king.row, rook.row), step(king.col, rook.col));
+        let is_piece = |pos: Position, shape| {
+            self.board[pos.row][pos.col] == Piece::Normal(Color::Turn(color), shape)
+        };
+        if !is_piece(king, 'K') || !is_piece(rook, 'R') {
+            return Err(MoveError::EmptySource);
+        }
+        let mut between = offset(king, direction);
+        while let Some(pos) = between.filter(|&pos| pos != rook) {
+            if !self.board[pos.row][pos.col].is_empty() {
+                return Err(MoveError::CastlingBlocked(color));
+            }
+            between = offset(pos, direction);
+        }
+
+        // Guaranteed to be on the board since the rook is at least 3 squares away
+        let rook_to = offset(king, direction).unwrap();
+        let king_to = offset(rook_to, direction).unwrap();
+        let king_piece = std::mem::take(&mut self.board[king.row][king.col]);
+        let rook_piece = std::mem::take(&mut self.board[rook.row][rook.col]);
+        self.board[king_to.row][king_to.col] = king_piece;
+        self.board[rook_to.row][rook_to.col] = rook_piece;
+        self.castling_king[index] = false;
+        self.castling_queen[index] = false;
+        self.draw_ply += 1;
+        self.turn = self.turn.next();
+        Ok(())
+    }
+
+    /// Applies `mv` like [`Board::make_move`], then promotes pawns and reports who is in check.
+    ///
+    /// A pawn that lands on the 8th rank from its player's side (the middle of the board) becomes a
+    /// queen of the same color, which is chess.com's Free-for-all rule. A player counts as in check
+    /// when any of their royal pieces (see [`Board::royal_squares`]) is attacked by a living piece of
+    /// another color. Only the standard pieces are considered as attackers, pawns capture diagonally
+    /// forward from their own player's side, and dead players are never reported as in check.
+    ///
+    /// ```
+    /// # use fen4::{Board, Move, Position};
+    /// let mut board = Board::default();
+    /// let report = board
+    ///     .apply_move_reporting(Move { from: Position { row: 1, col: 7 }, to: Position { row: 3, col: 7 } })
+    ///     .unwrap();
+    /// assert!(!report.promoted);
+    /// assert_eq!(report.checks, [false; 4]);
+    /// ```
+    pub fn apply_move_reporting(&mut self, mv: Move) -> Result<MoveReport, MoveError> {
+        let captured = self.make_move(mv.from, mv.to)?;
+        let square = &mut self.board[mv.to.row][mv.to.col];
+        let promoted = match square {
+            Piece::Normal(Color::Turn(color), shape)
+                if *shape == 'P' && promotes_on(*color, mv.to) =>
+            {
+                *shape = 'Q';
+                true
+            }
+            _ => false,
+        };
+        let mut checks = [false; 4];
+        for color in TurnColor::iter() {
+            let index = usize::from(color);
+            checks[index] = !self.dead[index] && self.is_in_check(color);
+        }
+        Ok(MoveReport {
+            captured,
+            promoted,
+            checks,
+        })
+    }
+}
@@ -0,0 +1,105 @@
+use thiserror::Error;
+
+use crate::moves::MoveError;
+use crate::types::*;
+
+/// Enum to store all ways [`replay_pgn4`] can fail. Moves are counted from 1 in the order they are
+/// played, ignoring move numbers.
+#[derive(Error, Clone, PartialEq, Eq, Debug)]
+pub enum Pgn4Error {
+    #[error("Move {0} '{1}' is not in a recognized format")]
+    BadMove(usize, String),
+    #[error("Move {0} is a castle, which is not supported")]
+    Castling(usize),
+    #[error("Move {0} moves a '{1}', but the piece on {2} is a different piece")]
+    WrongPiece(usize, char, Position),
+    #[error("Move {0} could not be applied because {1}")]
+    Illegal(usize, MoveError),
+}
+
+struct Pgn4Move {
+    shape: Option<char>,
+    from: Position,
+    to: Position,
+    promotion: Option<char>,
+}
+
+fn parse_move(text: &str) -> Option<Pgn4Move> {
+    let text = text.trim_end_matches(['+', '#', '!', '?']);
+    let (text, promotion) = match text.find('=') {
+        Some(equals) => {
+            let mut chars = text[equals + 1..].chars();
+            let shape = chars.next().filter(char::is_ascii_uppercase)?;
+            if chars.next().is_some() {
+                return None;
+            }
+            (&text[..equals], Some(shape))
+        }
+        None => (text, None),
+    };
+    let shape = text.chars().next().filter(char::is_ascii_uppercase);
+    let text = &text[shape.map_or(0, char::len_utf8)..];
+    let split = text.find(['-', 'x'])?;
+    let from = text[..split].parse().ok()?;
+    let to = text[split + 1..].parse().ok()?;
+    Some(Pgn4Move {
+        shape,
+        from,
+        to,
+        promotion,
+    })
+}
+
+/// Replays the moves of a PGN4 game starting from `start`, returning the board after each move.
+///
+/// `moves` is the move text of a PGN4 (the part after the tags) like
+/// `1. h2-h3 .. b7-c7 .. j13-j12 .. m7-l7 2. Qg1xk5+`. Move numbers and `..` are skipped, and each
+/// move is a square, `-` or `x`, and a square, optionally led by the letter of the moving piece and
+/// followed by a promotion like `=D`. Check and mate markers (`+` and `#`) and `!` and `?` are
+/// ignored.
+///
+/// This is a minimal version: the moves are applied with [`Board::make_move`], so legality isn't
+/// checked and the turn simply passes to the next color. Castling, comments, and other annotations
+/// aren't supported.
+///
+/// ```
+/// # use fen4::{Board, Color, Piece, TurnColor};
+/// let boards = fen4::replay_pgn4(&Board::default(), "1. h2-h3 .. b7-c7 .. Nj14-i12").unwrap();
+/// assert_eq!(boards.len(), 3);
+/// assert_eq!(boards[2].board[11][8], Piece::Normal(Color::Turn(TurnColor::Yellow), 'N'));
+/// assert_eq!(boards[2].turn, TurnColor::Green);
+/// ```
+pub fn replay_pgn4(start: &Board, moves: &str) -> Result<Vec<Board>, Pgn4Error> {
+    let mut board = start.clone();
+    let mut boards = Vec::new();
+    let tokens = moves.split_whitespace().filter(|token| {
+        let is_number =
+            matches!(token.strip_suffix('.'), Some(n) if n.chars().all(|c| c.is_ascii_digit()));
+        !is_number && *token != ".."
+    });
+    for (index, token) in tokens.enumerate() {
+        let number = index + 1;
+        if token.starts_with("O-O") {
+            return Err(Pgn4Error::Castling(number));
+        }
+        let mv = parse_move(token).ok_or_else(|| Pgn4Error::BadMove(number, token.to_string()))?;
+        if let Some(shape) = mv.shape {
+            match &board.board[mv.from.row][mv.from.col] {
+                Piece::Normal(_, actual) if *actual != shape => {
+                    return Err(Pgn4Error::WrongPiece(number, shape, mv.from))
+                }
+                _ => {}
+            }
+        }
+        board
+            .make_move(mv.from, mv.to)
+            .map_err(|e| Pgn4Error::Illegal(number, e))?;
+        if let Some(shape) = mv.promotion {
+            board
+                .promote(mv.to, shape)
+                .map_err(|e| Pgn4Error::Illegal(number, e))?;
+        }
+        boards.push(board.clone());
+    }
+    Ok(boards)
+}
@@ -1,7 +1,13 @@
+use std::collections::HashMap;
+
+/// Number of rows and of columns on the board, including the corners
+pub const BOARD_SIZE: usize = 14;
+
 /// Position on the board e.g. a4
 ///
 /// Both row and col should be in the range 0-13.
 #[derive(PartialEq, Eq, Clone, Copy, Debug, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Position {
     pub row: usize,
     pub col: usize,
@@ -9,6 +15,7 @@ pub struct Position {
 
 /// Simple enum for used to denote a turn / player.
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TurnColor {
     Red,
     Blue,
@@ -16,7 +23,31 @@ pub enum TurnColor {
     Green,
 }
 
+const TURN_ORDER: [TurnColor; 4] = [
+    TurnColor::Red,
+    TurnColor::Blue,
+    TurnColor::Yellow,
+    TurnColor::Green,
+];
+
 impl TurnColor {
+    /// All four colors in turn order, which is also the order used to index per-player arrays
+    ///
+    /// ```
+    /// # use fen4::TurnColor;
+    /// for (i, color) in TurnColor::all().iter().enumerate() {
+    ///     assert_eq!(i, usize::from(color));
+    /// }
+    /// ```
+    pub fn all() -> [TurnColor; 4] {
+        TURN_ORDER
+    }
+
+    /// Iterates over [`TurnColor::all`]
+    pub fn iter() -> impl Iterator<Item = TurnColor> {
+        TURN_ORDER.iter().copied()
+    }
+
     /// Rotates through colors in a clockwise direction
     ///
     /// ```
@@ -33,12 +64,118 @@ impl TurnColor {
             Green => Red,
         }
     }
+
+    /// Rotates through colors in a counter-clockwise direction
+    ///
+    /// ```
+    /// # use fen4::TurnColor;
+    /// assert_eq!(TurnColor::Green, TurnColor::Red.prev());
+    /// assert_eq!(TurnColor::Red, TurnColor::Blue.prev());
+    /// ```
+    pub fn prev(&self) -> Self {
+        use TurnColor::*;
+        match self {
+            Red => Green,
+            Blue => Red,
+            Yellow => Blue,
+            Green => Yellow,
+        }
+    }
+
+    /// The team a player is on in Teams mode
+    ///
+    /// ```
+    /// # use fen4::{Team, TurnColor};
+    /// assert_eq!(Team::RedYellow, TurnColor::Yellow.team());
+    /// ```
+    pub fn team(&self) -> Team {
+        use TurnColor::*;
+        match self {
+            Red | Yellow => Team::RedYellow,
+            Blue | Green => Team::BlueGreen,
+        }
+    }
+
+    /// The player sitting opposite, who is on the same team in Teams mode
+    pub fn teammate(&self) -> Self {
+        self.next().next()
+    }
+
+    /// The edge of the board a player's army starts on
+    ///
+    /// ```
+    /// # use fen4::{Edge, TurnColor};
+    /// assert_eq!(Edge::Bottom, TurnColor::Red.home_edge());
+    /// ```
+    pub fn home_edge(&self) -> Edge {
+        use TurnColor::*;
+        match self {
+            Red => Edge::Bottom,
+            Blue => Edge::Left,
+            Yellow => Edge::Top,
+            Green => Edge::Right,
+        }
+    }
+
+    /// The `(row, col)` step a player's pawns move forward, away from their [`TurnColor::home_edge`]
+    ///
+    /// Red moves up (increasing row), Blue right, Yellow down and Green left.
+    ///
+    /// ```
+    /// # use fen4::TurnColor;
+    /// assert_eq!((1, 0), TurnColor::Red.forward());
+    /// assert_eq!((0, -1), TurnColor::Green.forward());
+    /// ```
+    #[doc(alias = "pawn_forward")]
+    pub fn forward(&self) -> (isize, isize) {
+        use TurnColor::*;
+        match self {
+            Red => (1, 0),
+            Blue => (0, 1),
+            Yellow => (-1, 0),
+            Green => (0, -1),
+        }
+    }
+
+    /// Alias for [`TurnColor::forward`]
+    pub fn pawn_forward(&self) -> (isize, isize) {
+        self.forward()
+    }
+}
+
+/// The two teams in Teams mode, where partners sit opposite each other.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Team {
+    RedYellow,
+    BlueGreen,
+}
+
+/// An edge of the board as seen in the fen4 (Red at the bottom).
+///
+/// Bottom is row 0 and Left is column 0.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Edge {
+    Bottom,
+    Left,
+    Top,
+    Right,
+}
+
+/// Which rook a king castles with, see [`Board::castle`]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CastleSide {
+    King,
+    Queen,
 }
 
 /// Color modifier for pieces
 ///
 /// Includes normal pieces, dead pieces, and dead pieces that also track which player they came from.
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Color {
     Turn(TurnColor),
     Dead(Option<TurnColor>),
@@ -46,10 +183,7 @@ pub enum Color {
 
 impl Color {
     pub fn is_dead(self) -> bool {
-        match self {
-            Self::Dead(_) => true,
-            _ => false,
-        }
+        matches!(self, Self::Dead(_))
     }
 }
 
@@ -57,34 +191,148 @@ impl Color {
 /// The trick is to just use the character that the notation uses to represent that piece.
 ///
 /// Walls and empty cells are special, but everything else has a color as well.
-#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+///
+/// With the `multi-char-fairy` feature, pieces whose shape is more than one character (like `rAB`)
+/// are [`Piece::Fairy`]. Without it those are parse errors, and matching on the three variants keeps
+/// compiling. [`Piece::shape_str`] reads the shape of either kind of piece.
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Piece {
+    #[default]
     Empty,
     Wall,
     Normal(Color, char),
+    /// A piece with a shape of two or more characters. Single character shapes are always
+    /// [`Piece::Normal`], so each piece has only one representation.
+    ///
+    /// Fairy pieces don't move or attack in [`Board::legal_moves`] and the other move helpers, the
+    /// same as [`Piece::Normal`] pieces with an unknown shape.
+    #[cfg(feature = "multi-char-fairy")]
+    Fairy(Color, String),
 }
 
-impl Default for Piece {
-    fn default() -> Self {
-        Piece::Empty
-    }
+/// Typed view of a piece's shape for the standard chess pieces
+///
+/// Anything else is kept as [`PieceKind::Fairy`] with the character used in the fen4.
+/// Converting to and from `char` uses the fen4 letters.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PieceKind {
+    Pawn,
+    Knight,
+    Bishop,
+    Rook,
+    Queen,
+    King,
+    Fairy(char),
 }
 
 impl Piece {
     pub fn is_piece(&self) -> bool {
+        !matches!(self, Piece::Empty | Piece::Wall)
+    }
+    pub fn is_empty(&self) -> bool {
+        matches!(self, Piece::Empty)
+    }
+    /// The kind of piece, or `None` for empty squares, walls and multi-character [`Piece::Fairy`] pieces
+    ///
+    /// ```
+    /// # use fen4::{Color, Piece, PieceKind, TurnColor};
+    /// let knight = Piece::Normal(Color::Turn(TurnColor::Blue), 'N');
+    /// assert_eq!(knight.kind(), Some(PieceKind::Knight));
+    /// assert_eq!(Piece::Wall.kind(), None);
+    /// ```
+    pub fn kind(&self) -> Option<PieceKind> {
         match self {
-            Piece::Normal(_, _) => true,
-            _ => false,
+            Piece::Normal(_, shape) => Some((*shape).into()),
+            _ => None,
         }
     }
-    pub fn is_empty(&self) -> bool {
+    /// The color of the piece, or `None` for empty squares and walls
+    pub fn color(&self) -> Option<Color> {
+        match self {
+            Piece::Normal(color, _) => Some(*color),
+            #[cfg(feature = "multi-char-fairy")]
+            Piece::Fairy(color, _) => Some(*color),
+            _ => None,
+        }
+    }
+    // The color of the piece, for changing it in place
+    pub(crate) fn color_mut(&mut self) -> Option<&mut Color> {
+        match self {
+            Piece::Normal(color, _) => Some(color),
+            #[cfg(feature = "multi-char-fairy")]
+            Piece::Fairy(color, _) => Some(color),
+            _ => None,
+        }
+    }
+    /// The shape of the piece as it is written in a fen4, or `None` for empty squares and walls
+    ///
+    /// This works for both single character shapes and multi-character [`Piece::Fairy`] shapes.
+    ///
+    /// ```
+    /// # use fen4::{Color, Piece, TurnColor};
+    /// let queen = Piece::Normal(Color::Turn(TurnColor::Red), 'Q');
+    /// assert_eq!(queen.shape_str(), Some("Q".to_string()));
+    /// assert_eq!(Piece::Wall.shape_str(), None);
+    /// ```
+    pub fn shape_str(&self) -> Option<String> {
         match self {
-            Piece::Empty => true,
-            _ => false,
+            Piece::Normal(_, shape) => Some(shape.to_string()),
+            #[cfg(feature = "multi-char-fairy")]
+            Piece::Fairy(_, shape) => Some(shape.clone()),
+            _ => None,
+        }
+    }
+    /// The player the piece belongs to, including dead pieces that remember their player
+    ///
+    /// ```
+    /// # use fen4::{Color, Piece, TurnColor};
+    /// let dead = Piece::Normal(Color::Dead(Some(TurnColor::Green)), 'Q');
+    /// assert_eq!(dead.color(), Some(Color::Dead(Some(TurnColor::Green))));
+    /// assert_eq!(dead.turn_color(), Some(TurnColor::Green));
+    /// assert_eq!(Piece::Normal(Color::Dead(None), 'Q').turn_color(), None);
+    /// ```
+    pub fn turn_color(&self) -> Option<TurnColor> {
+        match self.color()? {
+            Color::Turn(tc) | Color::Dead(Some(tc)) => Some(tc),
+            Color::Dead(None) => None,
         }
     }
+    /// A copy of the piece owned by `color` instead, keeping its shape
+    ///
+    /// Empty squares and walls have no color and are returned unchanged.
+    ///
+    /// ```
+    /// # use fen4::{Color, Piece, TurnColor};
+    /// let queen = Piece::Normal(Color::Turn(TurnColor::Red), 'Q');
+    /// let dead = queen.with_color(Color::Dead(Some(TurnColor::Red)));
+    /// assert_eq!(dead, Piece::Normal(Color::Dead(Some(TurnColor::Red)), 'Q'));
+    /// assert_eq!(Piece::Wall.with_color(Color::Dead(None)), Piece::Wall);
+    /// ```
+    pub fn with_color(&self, color: Color) -> Piece {
+        let mut out = self.clone();
+        if let Some(c) = out.color_mut() {
+            *c = color;
+        }
+        out
+    }
 }
 
+// The empty squares the two knights are placed on for each chess960 knight index
+const CHESS960_KNIGHTS: [(u16, u16); 10] = [
+    (0, 0),
+    (0, 1),
+    (0, 2),
+    (0, 3),
+    (1, 1),
+    (1, 2),
+    (1, 3),
+    (2, 2),
+    (2, 3),
+    (3, 3),
+];
+
 /// The board representation of a 4 player chess game.
 /// Board can be converted to and from a String in the fen4 format
 ///     
@@ -126,6 +374,11 @@ impl Piece {
 /// 0, followed by an optional extra data section. Each of these is separated by a
 /// '-'. All of the arrays are information about the players with the leftmost data
 /// about Red and proceding clockwise.
+///
+/// `Hash` is derived, so it hashes the fields in the order they are declared. That order is kept
+/// stable so a given hasher produces the same value across versions of this crate, and a test pins
+/// the values for a few boards. The standard library's hashing of the underlying integers is only
+/// stable for a given platform, so hashes should not be persisted across machines.
 #[derive(PartialEq, Eq, Clone, Hash)]
 pub struct Board {
     pub turn: TurnColor,
@@ -135,10 +388,20 @@ pub struct Board {
     pub points: [u16; 4],
     pub draw_ply: usize,
     pub extra_options: Extra,
-    pub board: [[Piece; 14]; 14],
+    pub board: [[Piece; BOARD_SIZE]; BOARD_SIZE],
 }
 
-const DEFAULT_FEN: &str = "R-0,0,0,0-1,1,1,1-1,1,1,1-0,0,0,0-0-
+/// The game mode a board is from, see [`Board::game_mode`]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GameMode {
+    FreeForAll,
+    Teams,
+    Unknown,
+}
+
+/// The fen4 of the standard starting position, which is what [`Board::default`] produces.
+pub const DEFAULT_FEN: &str = "R-0,0,0,0-1,1,1,1-1,1,1,1-0,0,0,0-0-
 3,yR,yN,yB,yK,yQ,yB,yN,yR,3/
 3,yP,yP,yP,yP,yP,yP,yP,yP,3/
 14/
@@ -154,14 +417,90 @@ bR,bP,10,gP,gR/
 3,rP,rP,rP,rP,rP,rP,rP,rP,3/
 3,rR,rN,rB,rQ,rK,rB,rN,rR,3";
 
+// Back ranks of the default position, in order of increasing row or column
+const RED_BLUE_BACK_RANK: [char; 8] = ['R', 'N', 'B', 'Q', 'K', 'B', 'N', 'R'];
+const YELLOW_GREEN_BACK_RANK: [char; 8] = ['R', 'N', 'B', 'K', 'Q', 'B', 'N', 'R'];
+
+/// Builds the same board as parsing [`DEFAULT_FEN`], without doing any parsing.
 impl Default for Board {
     fn default() -> Self {
-        DEFAULT_FEN.parse::<Self>().unwrap()
+        use TurnColor::*;
+        let mut output = Board::empty();
+        let piece = |color, shape| Piece::Normal(Color::Turn(color), shape);
+        for i in 0..8 {
+            let (red_blue, yellow_green) = (RED_BLUE_BACK_RANK[i], YELLOW_GREEN_BACK_RANK[i]);
+            output.board[0][i + 3] = piece(Red, red_blue);
+            output.board[1][i + 3] = piece(Red, 'P');
+            output.board[i + 3][0] = piece(Blue, red_blue);
+            output.board[i + 3][1] = piece(Blue, 'P');
+            output.board[BOARD_SIZE - 1][i + 3] = piece(Yellow, yellow_green);
+            output.board[BOARD_SIZE - 2][i + 3] = piece(Yellow, 'P');
+            output.board[i + 3][BOARD_SIZE - 1] = piece(Green, yellow_green);
+            output.board[i + 3][BOARD_SIZE - 2] = piece(Green, 'P');
+        }
+        output
     }
 }
 
 impl Board {
-    pub fn chess960(mut n: u16) -> Board {
+    /// A board with no pieces or walls at all and the metadata of [`Board::default`].
+    ///
+    /// ```
+    /// # use fen4::Board;
+    /// let board = Board::empty();
+    /// assert!(board.board.iter().flatten().all(|p| p.is_empty()));
+    /// assert!(board.to_string().ends_with("14/\n14/\n14"));
+    /// ```
+    pub fn empty() -> Board {
+        Board {
+            turn: TurnColor::Red,
+            dead: [false; 4],
+            castling_king: [true; 4],
+            castling_queen: [true; 4],
+            points: [0; 4],
+            draw_ply: 0,
+            extra_options: Extra::default(),
+            board: Default::default(),
+        }
+    }
+
+    /// Like [`Board::empty`], but with the four 3x3 corners filled with [`Piece::Wall`].
+    pub fn empty_with_walls() -> Board {
+        let mut board = Board::empty();
+        for (row, pieces) in board.board.iter_mut().enumerate() {
+            for (col, piece) in pieces.iter_mut().enumerate() {
+                if !(Position { row, col }).is_playable() {
+                    *piece = Piece::Wall;
+                }
+            }
+        }
+        board
+    }
+
+    pub fn chess960(n: u16) -> Board {
+        let back_row = Board::chess960_back_rank(n);
+        let mut output = Board::default();
+        for (i, &shape) in back_row.iter().enumerate() {
+            output.board[0][i + 3] = Piece::Normal(Color::Turn(TurnColor::Red), shape);
+            output.board[i + 3][0] = Piece::Normal(Color::Turn(TurnColor::Blue), shape);
+            output.board[BOARD_SIZE - 1][10 - i] =
+                Piece::Normal(Color::Turn(TurnColor::Yellow), shape);
+            output.board[10 - i][BOARD_SIZE - 1] =
+                Piece::Normal(Color::Turn(TurnColor::Green), shape);
+        }
+        output
+    }
+
+    /// The back rank [`Board::chess960`] uses for `n`, as Red's pieces from d1 to k1.
+    ///
+    /// The other players get the same rank the way the default position is laid out: Blue from a4 to
+    /// a11, Yellow from k14 to d14, and Green from n11 to n4.
+    ///
+    /// ```
+    /// # use fen4::Board;
+    /// assert_eq!(Board::chess960_back_rank(519), ['R', 'N', 'B', 'Q', 'K', 'B', 'N', 'R']);
+    /// ```
+    pub fn chess960_back_rank(mut n: u16) -> [char; 8] {
         n -= 1; // chess.com used 1-960
                 // Mapping derived from
                 // https://en.wikipedia.org/wiki/Fischer_Random_Chess_numbering_scheme#Direct_derivation
@@ -189,32 +528,402 @@ impl Board {
             buffer[i] = to_place;
         }
         place(q, 'Q', &mut back_row);
-        let (knight1, knight2) = [
-            (0, 0),
-            (0, 1),
-            (0, 2),
-            (0, 3),
-            (1, 1),
-            (1, 2),
-            (1, 3),
-            (2, 2),
-            (2, 3),
-            (3, 3),
-        ][knights as usize];
+        let (knight1, knight2) = CHESS960_KNIGHTS[knights as usize];
         place(knight1, 'N', &mut back_row);
         place(knight2, 'N', &mut back_row);
         place(0, 'R', &mut back_row);
         place(0, 'K', &mut back_row);
         place(0, 'R', &mut back_row);
-        let mut output = Board::default();
-        for i in 0..8 {
-            output.board[0][i + 3] = Piece::Normal(Color::Turn(TurnColor::Red), back_row[i]);
-            output.board[i + 3][0] = Piece::Normal(Color::Turn(TurnColor::Blue), back_row[i]);
-            output.board[13][10 - i] = Piece::Normal(Color::Turn(TurnColor::Yellow), back_row[i]);
-            output.board[10 - i][13] = Piece::Normal(Color::Turn(TurnColor::Green), back_row[i]);
+        back_row
+    }
+
+    /// The chess960 number of Red's back rank, the inverse of [`Board::chess960`].
+    ///
+    /// Returns `None` if Red's back rank (the first rank between the corners) isn't a Fischer random
+    /// arrangement: one king between two rooks, a queen, two knights, and two bishops on opposite
+    /// colored squares. The other players' pieces are not checked.
+    ///
+    /// ```
+    /// # use fen4::Board;
+    /// assert_eq!(Board::default().chess960_number(), Some(519));
+    /// let mut board = Board::default();
+    /// // Both bishops on the same color
+    /// board.board[0].swap(5, 6);
+    /// assert_eq!(board.chess960_number(), None);
+    /// ```
+    pub fn chess960_number(&self) -> Option<u16> {
+        let mut back_row = ['?'; 8];
+        for (i, shape) in back_row.iter_mut().enumerate() {
+            match self.board[0][i + 3] {
+                Piece::Normal(Color::Turn(TurnColor::Red), s) => *shape = s,
+                _ => return None,
+            }
+        }
+        let odd_bishop = (0..4).find(|&i| back_row[2 * i + 1] == 'B')?;
+        let even_bishop = (0..4).find(|&i| back_row[2 * i] == 'B')?;
+        let mut rest: Vec<char> = back_row.iter().copied().filter(|&c| c != 'B').collect();
+        if rest.len() != 6 {
+            return None;
+        }
+        let q = rest.iter().position(|&c| c == 'Q')?;
+        rest.remove(q);
+        let mut knight_spots = (0..5).filter(|&i| rest[i] == 'N');
+        let first = knight_spots.next()?;
+        let second = knight_spots.next()?;
+        if knight_spots.next().is_some() {
+            return None;
         }
+        // The second knight is placed after the first, so it skips over it
+        let knights = CHESS960_KNIGHTS
+            .iter()
+            .position(|&placement| placement == (first as u16, second as u16 - 1))?;
+        rest.retain(|&c| c != 'N');
+        if rest != ['R', 'K', 'R'] {
+            return None;
+        }
+        let n = ((knights as u16 * 6 + q as u16) * 4 + even_bishop as u16) * 4 + odd_bishop as u16;
+        Some(n + 1)
+    }
+
+    /// Writes the fen4 on a single line, the form chess.com uses in URLs.
+    ///
+    /// This is the same as formatting with `{:#}` and parses back to the same board.
+    pub fn to_fen_oneline(&self) -> String {
+        format!("{:#}", self)
+    }
+
+    /// Returns a copy of the board with `piece` placed at `pos`.
+    ///
+    /// # Panics
+    /// Panics if `pos` is not on the board.
+    ///
+    /// ```
+    /// # use fen4::{Board, Piece};
+    /// let board = Board::default()
+    ///     .with_piece((1, 3).into(), Piece::Empty)
+    ///     .with_piece((0, 3).into(), Piece::Wall);
+    /// assert_eq!(board.board[0][3], Piece::Wall);
+    /// ```
+    pub fn with_piece(&self, pos: Position, piece: Piece) -> Board {
+        let mut output = self.clone();
+        output.board[pos.row][pos.col] = piece;
         output
     }
+
+    /// Compares every field except `turn`: `dead`, `castling_king`, `castling_queen`, `points`,
+    /// `draw_ply`, `extra_options` and `board`.
+    pub fn eq_ignoring_turn(&self, other: &Board) -> bool {
+        let Board {
+            turn: _,
+            dead,
+            castling_king,
+            castling_queen,
+            points,
+            draw_ply,
+            extra_options,
+            board,
+        } = self;
+        *dead == other.dead
+            && *castling_king == other.castling_king
+            && *castling_queen == other.castling_queen
+            && *points == other.points
+            && *draw_ply == other.draw_ply
+            && *extra_options == other.extra_options
+            && *board == other.board
+    }
+
+    /// Compares only what decides which moves can be made: `board`, `turn`, `castling_king` and
+    /// `castling_queen`.
+    ///
+    /// `dead`, `points`, `draw_ply` and `extra_options` (including en passant squares) are ignored,
+    /// so this is suited to detecting repeated positions. [`PartialEq`] still compares every field.
+    ///
+    /// ```
+    /// # use fen4::Board;
+    /// let scored = Board { points: [5, 0, 0, 0], draw_ply: 3, ..Board::default() };
+    /// assert!(scored.same_position(&Board::default()));
+    /// assert_ne!(scored, Board::default());
+    /// ```
+    pub fn same_position(&self, other: &Board) -> bool {
+        self.board == other.board
+            && self.turn == other.turn
+            && self.castling_king == other.castling_king
+            && self.castling_queen == other.castling_queen
+    }
+
+    /// All 196 squares in row-major order starting from a1, so square `(row, col)` is at index `row * 14 + col`.
+    pub fn to_flat(&self) -> Vec<Piece> {
+        self.board.iter().flatten().cloned().collect()
+    }
+
+    /// Inverse of [`Board::to_flat`]. The metadata is the same as [`Board::default`].
+    ///
+    /// Fails if `flat` does not have exactly 196 pieces.
+    #[allow(clippy::result_unit_err)]
+    pub fn from_flat(flat: &[Piece]) -> Result<Board, ()> {
+        if flat.len() != BOARD_SIZE * BOARD_SIZE {
+            return Err(());
+        }
+        let mut output = Board::default();
+        for (row, pieces) in flat.chunks(BOARD_SIZE).enumerate() {
+            output.board[row].clone_from_slice(pieces);
+        }
+        Ok(output)
+    }
+
+    /// Every position holding a piece equal to `piece`, ordered by row then column.
+    ///
+    /// ```
+    /// # use fen4::{Board, Color, Piece, Position, TurnColor};
+    /// let red_king = Piece::Normal(Color::Turn(TurnColor::Red), 'K');
+    /// assert_eq!(Board::default().find(&red_king), vec![Position { row: 0, col: 7 }]);
+    /// ```
+    pub fn find(&self, piece: &Piece) -> Vec<Position> {
+        self.positions_of(piece).collect()
+    }
+
+    /// The rows from rank 14 down to rank 1, the order they are written in a fen4 and by [`Display`](std::fmt::Display).
+    ///
+    /// ```
+    /// # use fen4::{Board, Color, Piece, TurnColor};
+    /// let board = Board::default();
+    /// let top = board.rows_top_down().next().unwrap();
+    /// assert_eq!(top[6], Piece::Normal(Color::Turn(TurnColor::Yellow), 'K'));
+    /// ```
+    pub fn rows_top_down(&self) -> impl Iterator<Item = &[Piece; BOARD_SIZE]> {
+        self.board.iter().rev()
+    }
+
+    /// The rows from rank 1 up to rank 14, the same order as the indices of [`Board::board`].
+    pub fn rows_bottom_up(&self) -> impl Iterator<Item = &[Piece; BOARD_SIZE]> {
+        self.board.iter()
+    }
+
+    /// Every square that isn't empty with what is on it, ordered by row then column.
+    ///
+    /// Walls are included. Collecting the pairs back into a [`Board`] rebuilds the squares.
+    ///
+    /// ```
+    /// # use fen4::{Board, Color, Piece, TurnColor};
+    /// // Remove all of Green's pieces
+    /// let board: Board = Board::default()
+    ///     .pieces()
+    ///     .filter(|(_, piece)| piece.color() != Some(Color::Turn(TurnColor::Green)))
+    ///     .collect();
+    /// assert_eq!(board.material(), [39, 39, 39, 0]);
+    /// ```
+    pub fn pieces(&self) -> impl Iterator<Item = (Position, Piece)> + '_ {
+        self.positions_where(|p| !p.is_empty())
+            .map(move |pos| (pos, self.board[pos.row][pos.col].clone()))
+    }
+
+    /// The first position [`Board::find`] would return
+    pub fn find_first(&self, piece: &Piece) -> Option<Position> {
+        self.positions_of(piece).next()
+    }
+
+    /// Positions of the living pieces of `color` with the given shape, ordered by row then column.
+    ///
+    /// ```
+    /// # use fen4::{Board, TurnColor};
+    /// assert_eq!(Board::default().iter_shape_of(TurnColor::Red, 'P').count(), 8);
+    /// ```
+    pub fn iter_shape_of(
+        &self,
+        color: TurnColor,
+        shape: char,
+    ) -> impl Iterator<Item = Position> + '_ {
+        self.positions_where(
+            move |p| matches!(p, Piece::Normal(Color::Turn(c), s) if *c == color && *s == shape),
+        )
+    }
+
+    fn positions_of<'a>(&'a self, piece: &'a Piece) -> impl Iterator<Item = Position> + 'a {
+        self.positions_where(move |p| p == piece)
+    }
+
+    fn positions_where<'a>(
+        &'a self,
+        pred: impl Fn(&Piece) -> bool + Copy + 'a,
+    ) -> impl Iterator<Item = Position> + 'a {
+        self.board
+            .iter()
+            .enumerate()
+            .flat_map(move |(row, pieces)| {
+                pieces
+                    .iter()
+                    .enumerate()
+                    .filter(move |(_, p)| pred(p))
+                    .map(move |(col, _)| Position { row, col })
+            })
+    }
+
+    /// Checks if two boards have the same pieces on every square, ignoring all of the metadata.
+    pub fn same_pieces(&self, other: &Board) -> bool {
+        self.board == other.board
+    }
+
+    /// A copy of the board with `extra_options` reset to the default, keeping the squares and the
+    /// rest of the metadata.
+    ///
+    /// ```
+    /// # use fen4::Board;
+    /// let mut board = Board::default();
+    /// board.extra_options.lives = Some([2, 2, 2, 2]);
+    /// assert_eq!(board.without_extras(), Board::default());
+    /// ```
+    pub fn without_extras(&self) -> Board {
+        Board {
+            extra_options: Extra::default(),
+            ..self.clone()
+        }
+    }
+
+    /// Point value of a piece shape using chess.com's 4 player values.
+    ///
+    /// Pawns are worth 1, knights and bishops 3, rooks 5, queens 9, and kings and any other shape 0.
+    pub fn piece_value(shape: char) -> u32 {
+        match shape {
+            'P' => 1,
+            'N' | 'B' => 3,
+            'R' => 5,
+            'Q' => 9,
+            _ => 0,
+        }
+    }
+
+    /// Sums the value of every living piece each player owns, indexed by `usize::from(color)`.
+    ///
+    /// Values come from [`Board::piece_value`] and dead pieces are not counted.
+    pub fn material(&self) -> [u32; 4] {
+        self.material_with(Board::piece_value)
+    }
+
+    /// Like [`Board::material`], but `value` decides what each shape is worth.
+    ///
+    /// ```
+    /// # use fen4::Board;
+    /// // Count fairy amazons ('A') as 12 points
+    /// let material = Board::default().material_with(|shape| match shape {
+    ///     'A' => 12,
+    ///     s => Board::piece_value(s),
+    /// });
+    /// assert_eq!(material, [39; 4]);
+    /// ```
+    pub fn material_with(&self, value: impl Fn(char) -> u32) -> [u32; 4] {
+        let mut totals = [0; 4];
+        for piece in self.board.iter().flatten() {
+            if let Piece::Normal(Color::Turn(c), shape) = piece {
+                totals[usize::from(c)] += value(*shape);
+            }
+        }
+        totals
+    }
+
+    /// How many living pieces of each shape each player has, keyed by color and shape.
+    ///
+    /// Like [`Board::material`], dead pieces are left out entirely, even when they remember the
+    /// color they came from: they can't move or be used again, so they aren't counted as anyone's.
+    /// Shapes a player has none of are not in the map.
+    ///
+    /// ```
+    /// # use fen4::{Board, TurnColor};
+    /// let counts = Board::default().piece_counts();
+    /// assert_eq!(counts[&(TurnColor::Blue, 'P')], 8);
+    /// assert_eq!(counts[&(TurnColor::Green, 'K')], 1);
+    /// assert_eq!(counts.get(&(TurnColor::Red, 'A')), None);
+    /// ```
+    pub fn piece_counts(&self) -> HashMap<(TurnColor, char), usize> {
+        let mut counts = HashMap::new();
+        for piece in self.board.iter().flatten() {
+            if let Piece::Normal(Color::Turn(c), shape) = piece {
+                *counts.entry((*c, *shape)).or_insert(0) += 1;
+            }
+        }
+        counts
+    }
+
+    /// Every dead piece on the board, whether or not it remembers its color, ordered by row then column.
+    ///
+    /// ```
+    /// # use fen4::{Board, Color, Piece, TurnColor};
+    /// let mut board = Board::default();
+    /// board.resign(TurnColor::Blue);
+    /// let dead = board.dead_pieces();
+    /// assert_eq!(dead.len(), 16);
+    /// assert_eq!(dead[0], Piece::Normal(Color::Dead(Some(TurnColor::Blue)), 'R'));
+    /// ```
+    pub fn dead_pieces(&self) -> Vec<Piece> {
+        self.board
+            .iter()
+            .flatten()
+            .filter(|piece| matches!(piece.color(), Some(Color::Dead(_))))
+            .cloned()
+            .collect()
+    }
+
+    /// Every living piece of `color` on the board, ordered by row then column.
+    pub fn living_pieces(&self, color: TurnColor) -> Vec<Piece> {
+        self.board
+            .iter()
+            .flatten()
+            .filter(|piece| piece.color() == Some(Color::Turn(color)))
+            .cloned()
+            .collect()
+    }
+
+    /// Guesses whether the board comes from a Free-for-all or Teams game.
+    ///
+    /// The fen4 format is shared between the two modes, so this is only a heuristic:
+    ///   - Any points or any dead player means [`GameMode::FreeForAll`], because Teams doesn't use either.
+    ///   - Otherwise, if some piece has been captured (see [`Board::captured_since_default`]) it is
+    ///     [`GameMode::Teams`], because a capture in Free-for-all would have scored points.
+    ///   - Otherwise nothing distinguishes the modes and the result is [`GameMode::Unknown`].
+    ///
+    /// Boards that did not start from the default position can be misclassified as Teams.
+    pub fn game_mode(&self) -> GameMode {
+        if self.points != [0; 4] || self.dead != [false; 4] {
+            GameMode::FreeForAll
+        } else if self
+            .captured_since_default()
+            .iter()
+            .any(|lost| !lost.is_empty())
+        {
+            GameMode::Teams
+        } else {
+            GameMode::Unknown
+        }
+    }
+
+    /// Shapes of the pieces each player has lost compared to the default starting position.
+    ///
+    /// The output is indexed by `usize::from(color)` and each list is sorted. Pieces are only
+    /// matched by shape, so this is an approximation intended for captured piece displays:
+    ///   - A promoted pawn is reported as a lost pawn, and the piece it became is ignored as an extra.
+    ///   - Dead pieces still count as belonging to the player they came from.
+    ///   - Boards that did not start from the default setup (chess960, fairy variants) give meaningless results.
+    pub fn captured_since_default(&self) -> [Vec<char>; 4] {
+        let mut missing: [Vec<char>; 4] = Default::default();
+        for piece in Board::default().board.iter().flatten() {
+            if let Piece::Normal(Color::Turn(c), shape) = piece {
+                missing[usize::from(c)].push(*shape);
+            }
+        }
+        for piece in self.board.iter().flatten() {
+            if let Piece::Normal(Color::Turn(c), shape)
+            | Piece::Normal(Color::Dead(Some(c)), shape) = piece
+            {
+                let lost = &mut missing[usize::from(c)];
+                if let Some(i) = lost.iter().position(|s| s == shape) {
+                    lost.swap_remove(i);
+                }
+            }
+        }
+        for lost in &mut missing {
+            lost.sort_unstable();
+        }
+        missing
+    }
 }
 /// Additional options in the FEN4 format stored as a list of key value pairs.
 ///
@@ -237,6 +946,9 @@ impl Board {
 ///     - This is neccessary notation because some types of fairy pawns move diagonally. Without the extra information it could be ambiguous.
 ///   - `'lives':(9,6,4,0)`
 ///     - Indicates number of lives left for the N-check variant
+///   - `'checks':(0,2,1,0)`
+///     - The number of times each player has been checked in the N-check variant
+///     - The label is [`CHECKS_TAG`] since exports don't agree on a name for it
 ///   - `'pawnsBaseRank':8`
 ///     - The rank on which pawns can jump forward 2 square (default:2)
 ///     - a value of `0` indicates pawns never move more than 1
@@ -244,6 +956,8 @@ impl Board {
 ///     - Used to indicate which piece should be considered the king for purposes of checks
 ///     - Neccessary in cases of multiple kings (of the same color) or for making a different piece act as the leader
 ///     - This seems to have previously been called 'kingSquares'
+///     - A player with several royal pieces has their squares separated by spaces, like
+///       `('h1 k1','','','')`. chess.com's exports only use one square per player.
 ///   - `'uniquify':94403`
 ///     - It is not clear what this actually does
 ///   - `'resigned':(true,true,false,false)` and `'flagged':(false,true,false,false)`
@@ -255,19 +969,37 @@ impl Board {
 ///   - `'zombieType':('','','','muncher')`
 ///     - Used to change the behaviour of zombies
 ///     - Possible types include muncher, comfuter, checker, ranter, and possibly more
+///   - `'eliminated':(2,0,1,0)`
+///     - The order players were knocked out in: 1 for the first player out, 2 for the second, and
+///       0 for players that are still in
+///     - The label is [`ELIMINATED_TAG`] since the name chess.com uses for it isn't confirmed
 ///
 /// The labels have a preferred order. The preferred order is the order of the fields of the struct.
 ///
 /// gameOver is an additional option, but only seems to appear in internal messages.
-/// It would fit somwhere between flagged and enPassant in terms of preferred order and stores
-/// the message that shows up at the end of the game.
+/// It stores the message that shows up at the end of the game. Where chess.com puts it is only
+/// known to be somewhere between flagged and enPassant, so like every other tag it is written in
+/// field order: right after stalemated and before zombieImmune.
 ///
 /// This will also use gameOver to represent final messages, but will not specifically try to be
 /// compatable with chess.com's internal messages.
-#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+///
+/// Tags that aren't recognized (for example from a newer version of chess.com) are kept in `unknown`
+/// as the raw label and value text, in the order they were seen. The recognized tags are written in
+/// the preferred order, and each unknown tag is written right after the recognized tag it followed
+/// in the parsed fen4, or first if it came before all of them. Unknown tags that weren't parsed,
+/// like ones pushed onto `unknown` directly, are written after everything else. `tag_order` remembers the order tags were parsed in so
+/// [`Board::to_string_preserving_order`] can reproduce it, and for the same reason `nulls` remembers
+/// which boolean array slots were written as `null` rather than `false`.
+///
+/// `tag_order` and `nulls` only affect how the tags are written, so they are left out when comparing
+/// or hashing an `Extra`. The other fields are hashed in the order they are declared.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Extra {
-    pub royal: [Option<Position>; 4],
+    pub royal: [Vec<Position>; 4],
     pub lives: Option<[usize; 4]>,
+    pub checks: Option<[usize; 4]>,
     pub resigned: [bool; 4],
     pub flagged: [bool; 4],
     pub stalemated: [bool; 4],
@@ -278,6 +1010,172 @@ pub struct Extra {
     pub pawnbaserank: usize,
     pub uniquify: usize,
     pub std2pc: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub eliminated: [usize; 4],
+    pub unknown: Vec<(String, String)>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub tag_order: TagOrder,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub nulls: NullSlots,
+}
+
+/// The label used for [`Extra::checks`] when parsing and writing a fen4.
+pub const CHECKS_TAG: &str = "checks";
+
+/// The label used for [`Extra::eliminated`] when parsing and writing a fen4.
+pub const ELIMINATED_TAG: &str = "eliminated";
+
+impl Extra {
+    /// The players that have been eliminated according to [`Extra::eliminated`], first out first.
+    ///
+    /// ```
+    /// # use fen4::{Extra, TurnColor};
+    /// let extra: Extra = "{'eliminated':(2,0,1,0)}".parse().unwrap();
+    /// assert_eq!(extra.elimination_order(), vec![TurnColor::Yellow, TurnColor::Red]);
+    /// ```
+    pub fn elimination_order(&self) -> Vec<TurnColor> {
+        let mut out: Vec<TurnColor> = TurnColor::iter()
+            .filter(|&color| self.eliminated[usize::from(color)] != 0)
+            .collect();
+        out.sort_by_key(|&color| self.eliminated[usize::from(color)]);
+        out
+    }
+}
+
+/// The order tags were parsed in, used by [`Board::to_string_preserving_order`].
+///
+/// This only affects how the tags are written, so [`Extra`] ignores it when comparing or hashing.
+#[derive(Debug, PartialEq, Eq, Clone, Default, Hash)]
+pub struct TagOrder(Vec<String>);
+
+impl TagOrder {
+    /// The labels in the order they were seen, with 'kingSquares' recorded as 'royal'
+    pub fn labels(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(String::as_str)
+    }
+
+    pub(crate) fn push(&mut self, label: &str) {
+        self.0.push(Self::normalize(label).to_string());
+    }
+
+    // Adds `label` at the end unless it was already seen
+    pub(crate) fn push_if_missing(&mut self, label: &str) {
+        let label = Self::normalize(label);
+        if !self.labels().any(|l| l == label) {
+            self.0.push(label.to_string());
+        }
+    }
+
+    fn normalize(label: &str) -> &str {
+        if label == "kingSquares" {
+            "royal"
+        } else {
+            label
+        }
+    }
+}
+
+/// The slots of boolean array tags (like `'resigned':(null,true,false,false)`) that were written as
+/// `null` instead of `false`, used by [`Board::to_string_preserving_order`].
+///
+/// A `null` slot is read as `false`. Like [`TagOrder`] this only affects how the tags are written, so
+/// [`Extra`] ignores it when comparing or hashing.
+#[derive(Debug, PartialEq, Eq, Clone, Default, Hash)]
+pub struct NullSlots(Vec<(String, usize)>);
+
+impl NullSlots {
+    /// Whether slot `index` of the tag `label` was written as `null`
+    pub fn is_null(&self, label: &str, index: usize) -> bool {
+        self.0.iter().any(|(l, i)| l == label && *i == index)
+    }
+
+    pub(crate) fn push(&mut self, label: &str, index: usize) {
+        self.0.push((label.to_string(), index));
+    }
+
+    pub(crate) fn clear(&mut self, label: &str) {
+        self.0.retain(|(l, _)| l != label);
+    }
+}
+
+impl PartialEq for Extra {
+    fn eq(&self, other: &Self) -> bool {
+        let Extra {
+            royal,
+            lives,
+            checks,
+            resigned,
+            flagged,
+            stalemated,
+            game_over,
+            zombie_immune,
+            zombie_type,
+            enpassant,
+            pawnbaserank,
+            uniquify,
+            std2pc,
+            eliminated,
+            unknown,
+            tag_order: _,
+            nulls: _,
+        } = self;
+        *royal == other.royal
+            && *lives == other.lives
+            && *checks == other.checks
+            && *resigned == other.resigned
+            && *flagged == other.flagged
+            && *stalemated == other.stalemated
+            && *game_over == other.game_over
+            && *zombie_immune == other.zombie_immune
+            && *zombie_type == other.zombie_type
+            && *enpassant == other.enpassant
+            && *pawnbaserank == other.pawnbaserank
+            && *uniquify == other.uniquify
+            && *std2pc == other.std2pc
+            && *eliminated == other.eliminated
+            && *unknown == other.unknown
+    }
+}
+
+impl Eq for Extra {}
+
+impl std::hash::Hash for Extra {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        let Extra {
+            royal,
+            lives,
+            checks,
+            resigned,
+            flagged,
+            stalemated,
+            game_over,
+            zombie_immune,
+            zombie_type,
+            enpassant,
+            pawnbaserank,
+            uniquify,
+            std2pc,
+            eliminated,
+            unknown,
+            tag_order: _,
+            nulls: _,
+        } = self;
+        royal.hash(state);
+        lives.hash(state);
+        checks.hash(state);
+        resigned.hash(state);
+        flagged.hash(state);
+        stalemated.hash(state);
+        game_over.hash(state);
+        zombie_immune.hash(state);
+        zombie_type.hash(state);
+        enpassant.hash(state);
+        pawnbaserank.hash(state);
+        uniquify.hash(state);
+        std2pc.hash(state);
+        eliminated.hash(state);
+        unknown.hash(state);
+    }
 }
 
 impl Default for Extra {
@@ -285,6 +1183,7 @@ impl Default for Extra {
         Self {
             royal: Default::default(),
             lives: None,
+            checks: None,
             resigned: Default::default(),
             flagged: Default::default(),
             stalemated: Default::default(),
@@ -295,6 +1194,23 @@ impl Default for Extra {
             pawnbaserank: 2,
             uniquify: 0,
             std2pc: false,
+            eliminated: [0; 4],
+            unknown: Vec::new(),
+            tag_order: TagOrder::default(),
+            nulls: NullSlots::default(),
         }
     }
 }
+
+/// A value for one of the tags in [`Extra`], used with [`Board::set_extra`].
+///
+/// These mirror the value types the tagged format is known to use.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ExtraValue {
+    String(String),
+    Number(usize),
+    Boolean(bool),
+    /// One value per player, starting with Red and proceeding clockwise
+    Array(Vec<ExtraValue>),
+}
@@ -0,0 +1,203 @@
+use std::iter::once;
+
+use thiserror::Error;
+
+use crate::conversions::in_std2pc_area;
+use crate::moves::castling_homes;
+use crate::types::*;
+
+/// Ways a parsed [`Board`] can be inconsistent, found by [`Board::validate`]
+#[derive(Error, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BoardError {
+    #[error("There is a piece on {0}, which is in one of the corners")]
+    PieceInCorner(Position),
+    #[error("{0:?} is not on the board")]
+    OffBoard(Position),
+    #[error("The royal square for {0} is {1}, but that is not one of their pieces")]
+    RoyalNotOwned(TurnColor, Position),
+    #[error("{0} is dead but still has living pieces")]
+    DeadWithPieces(TurnColor),
+    #[error("{0} is alive but has no lives left")]
+    NoLives(TurnColor),
+    #[error("There is a piece on {0}, which is outside the 8x8 area of a two player board")]
+    OutsideStd2pcArea(Position),
+    #[error("The piece on {0} belongs to {1}, but only Red and Yellow play on a two player board")]
+    Std2pcColor(Position, TurnColor),
+}
+
+fn is_living(piece: &Piece, color: TurnColor) -> bool {
+    piece.color() == Some(Color::Turn(color))
+}
+
+impl Board {
+    /// Checks that the board makes sense beyond having the right dimensions.
+    ///
+    /// Every problem found is returned rather than just the first, in the order:
+    ///   - Pieces in the 3x3 corners (walls are fine).
+    ///   - `royal` and `enpassant` squares that are off the board.
+    ///   - For each player: a `royal` square that doesn't hold one of their pieces while alive, being
+    ///     `dead` while still having living pieces, and being alive with 0 `lives`.
+    ///   - On two player boards, the problems found by [`Board::validate_std2pc`].
+    ///
+    /// `points` are not checked since any `u16` is a valid score.
+    ///
+    /// ```
+    /// # use fen4::{Board, BoardError, TurnColor};
+    /// assert_eq!(Board::default().validate(), Ok(()));
+    /// let mut board = Board::default();
+    /// board.dead[2] = true;
+    /// assert_eq!(board.validate(), Err(vec![BoardError::DeadWithPieces(TurnColor::Yellow)]));
+    /// ```
+    pub fn validate(&self) -> Result<(), Vec<BoardError>> {
+        use BoardError::*;
+        let mut errors = Vec::new();
+        for (row, pieces) in self.board.iter().enumerate() {
+            for (col, piece) in pieces.iter().enumerate() {
+                let pos = Position { row, col };
+                if piece.is_piece() && !pos.is_playable() {
+                    errors.push(PieceInCorner(pos));
+                }
+            }
+        }
+        let extra = &self.extra_options;
+        let squares = extra.royal.iter().flatten().chain(
+            extra
+                .enpassant
+                .iter()
+                .flatten()
+                .flat_map(|(a, b)| once(a).chain(once(b))),
+        );
+        for pos in squares {
+            if pos.row >= BOARD_SIZE || pos.col >= BOARD_SIZE {
+                errors.push(OffBoard(*pos));
+            }
+        }
+        for color in TurnColor::iter() {
+            let index = usize::from(color);
+            for &pos in &extra.royal[index] {
+                let on_board = pos.row < BOARD_SIZE && pos.col < BOARD_SIZE;
+                if on_board && !self.dead[index] && !is_living(&self.board[pos.row][pos.col], color)
+                {
+                    errors.push(RoyalNotOwned(color, pos));
+                }
+            }
+            if self.dead[index] && self.board.iter().flatten().any(|p| is_living(p, color)) {
+                errors.push(DeadWithPieces(color));
+            }
+            if let Some(lives) = extra.lives {
+                if lives[index] == 0 && !self.dead[index] {
+                    errors.push(NoLives(color));
+                }
+            }
+        }
+        if let Err(std2pc_errors) = self.validate_std2pc() {
+            errors.extend(std2pc_errors);
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Whether the board is a two player game, which is marked by the `std2pc` tag.
+    ///
+    /// Two player boards are an 8x8 game embedded in the 14x14 grid: ranks 1-8 of files d-k, the
+    /// same area [`Board::to_standard_fen_2pc`] writes out.
+    pub fn is_std2pc(&self) -> bool {
+        self.extra_options.std2pc
+    }
+
+    /// Checks that a two player board only uses its 8x8 area and only has Red and Yellow pieces.
+    ///
+    /// Every piece outside the area is reported with [`BoardError::OutsideStd2pcArea`] (walls and
+    /// empty squares are fine anywhere), and every piece belonging to Blue or Green, living or dead,
+    /// with [`BoardError::Std2pcColor`]. Boards that aren't [`Board::is_std2pc`] are always fine.
+    /// [`Board::validate`] includes these checks.
+    ///
+    /// ```
+    /// # use fen4::{Board, BoardError, Position};
+    /// let mut board = Board::default();
+    /// assert_eq!(board.validate_std2pc(), Ok(()));
+    /// board.extra_options.std2pc = true;
+    /// let errors = board.validate_std2pc().unwrap_err();
+    /// assert_eq!(errors[0], BoardError::OutsideStd2pcArea(Position { row: 3, col: 0 }));
+    /// ```
+    pub fn validate_std2pc(&self) -> Result<(), Vec<BoardError>> {
+        if !self.is_std2pc() {
+            return Ok(());
+        }
+        let mut errors = Vec::new();
+        for (pos, piece) in self.pieces() {
+            if !piece.is_piece() {
+                continue;
+            }
+            if !in_std2pc_area(pos) {
+                errors.push(BoardError::OutsideStd2pcArea(pos));
+            }
+            match piece.turn_color() {
+                Some(color @ TurnColor::Blue) | Some(color @ TurnColor::Green) => {
+                    errors.push(BoardError::Std2pcColor(pos, color))
+                }
+                _ => {}
+            }
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Repairs the common inconsistencies of imported fen4s and describes each change made.
+    ///
+    /// In order, for each player:
+    ///   - Their `enpassant` square is cleared if it is their turn, since en passant is only possible
+    ///     until the player who pushed the pawn moves again.
+    ///   - If they are `dead`, their remaining living pieces become dead pieces, like [`Board::kill_player`].
+    ///   - King side and queen side castling rights are dropped if their king or the matching rook is
+    ///     not on its square from the default position.
+    ///
+    /// An empty log means the board was left unchanged.
+    ///
+    /// ```
+    /// # use fen4::Board;
+    /// let mut board = Board::default();
+    /// assert!(board.sanity_fix().is_empty());
+    /// board.board[0][10] = fen4::Piece::Empty;
+    /// assert_eq!(board.sanity_fix(), vec!["Removed Red's king side castling"]);
+    /// assert_eq!(board.castling_king, [false, true, true, true]);
+    /// ```
+    pub fn sanity_fix(&mut self) -> Vec<String> {
+        let mut log = Vec::new();
+        for color in TurnColor::iter() {
+            let index = usize::from(color);
+            if color == self.turn && self.extra_options.enpassant[index].take().is_some() {
+                log.push(format!("Cleared {:?}'s stale en passant", color));
+            }
+            if self.dead[index] && self.board.iter().flatten().any(|p| is_living(p, color)) {
+                self.kill_player(color);
+                log.push(format!(
+                    "Turned {:?}'s living pieces into dead pieces",
+                    color
+                ));
+            }
+            let [king, king_rook, queen_rook] = castling_homes(color);
+            let home = |pos: Position, shape: char| {
+                self.board[pos.row][pos.col] == Piece::Normal(Color::Turn(color), shape)
+            };
+            let king_home = home(king, 'K');
+            let keep_king_side = king_home && home(king_rook, 'R');
+            let keep_queen_side = king_home && home(queen_rook, 'R');
+            if self.castling_king[index] && !keep_king_side {
+                self.castling_king[index] = false;
+                log.push(format!("Removed {:?}'s king side castling", color));
+            }
+            if self.castling_queen[index] && !keep_queen_side {
+                self.castling_queen[index] = false;
+                log.push(format!("Removed {:?}'s queen side castling", color));
+            }
+        }
+        log
+    }
+}
@@ -0,0 +1,76 @@
+use crate::types::*;
+
+// Kinds of feature that get their own key, kept in the top bits so they can't collide
+const SQUARE: u64 = 0;
+const TURN: u64 = 1 << 62;
+const CASTLING: u64 = 2 << 62;
+
+// SplitMix64's output function, which turns each feature into a well mixed key
+fn key(feature: u64) -> u64 {
+    let mut z = feature.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+// A number for each kind of occupied square. The shape fits in 21 bits above the color, and
+// multi-character shapes are folded into 21 bits with FNV-1a and marked with the bit above that.
+fn piece_code(piece: &Piece) -> Option<u64> {
+    let color = match piece {
+        Piece::Empty => return None,
+        Piece::Wall => return Some(1),
+        piece => piece.color()?,
+    };
+    let color = match color {
+        Color::Turn(c) => 2 + usize::from(c) as u64,
+        Color::Dead(None) => 6,
+        Color::Dead(Some(c)) => 7 + usize::from(c) as u64,
+    };
+    let shape = match piece {
+        Piece::Normal(_, shape) => u64::from(u32::from(*shape)),
+        #[cfg(feature = "multi-char-fairy")]
+        Piece::Fairy(_, shape) => {
+            let hash = shape.bytes().fold(0xcbf2_9ce4_8422_2325u64, |hash, byte| {
+                (hash ^ u64::from(byte)).wrapping_mul(0x100_0000_01b3)
+            });
+            1 << 21 | (hash & 0x1f_ffff)
+        }
+        _ => 0,
+    };
+    Some(shape << 8 | color)
+}
+
+impl Board {
+    /// A Zobrist hash of the fields compared by [`Board::same_position`].
+    ///
+    /// Every occupied square, the turn, and each castling right that is still available has a fixed
+    /// 64 bit key, and the hash is all of those keys XORed together. The keys are derived from the
+    /// square, color, and shape with a fixed mixing function instead of a random table, so any shape
+    /// (including fairy pieces) has a key and the hash of a board is the same on every run, platform,
+    /// and version of this crate. That makes it usable as a transposition table key that is saved
+    /// to disk. Tests pin the values for a few boards and changing them is a breaking change.
+    ///
+    /// ```
+    /// # use fen4::Board;
+    /// let board = Board::default();
+    /// let scored = Board { points: [5, 0, 0, 0], ..Board::default() };
+    /// assert_eq!(board.position_hash(), scored.position_hash());
+    /// assert_ne!(board.position_hash(), board.rotated(1).position_hash());
+    /// ```
+    pub fn position_hash(&self) -> u64 {
+        let mut hash = key(TURN | usize::from(self.turn) as u64);
+        for (square, piece) in self.board.iter().flatten().enumerate() {
+            if let Some(code) = piece_code(piece) {
+                hash ^= key(SQUARE | (square as u64) << 32 | code);
+            }
+        }
+        for (side, rights) in [self.castling_king, self.castling_queen].iter().enumerate() {
+            for (player, &allowed) in rights.iter().enumerate() {
+                if allowed {
+                    hash ^= key(CASTLING | (side as u64) << 2 | player as u64);
+                }
+            }
+        }
+        hash
+    }
+}
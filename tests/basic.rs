@@ -21,11 +21,21 @@ bR,bP,10,gP,gR/
     assert_eq!(default_fen, string, "FromStr and Display are not inverses");
     let board2: Board = string.parse().unwrap();
     assert_eq!(board, board2, "FromStr and Display are not inverses2");
+    assert_eq!(default_fen, fen4::DEFAULT_FEN);
+    assert_eq!(board, Board::default());
 }
 
 #[test]
-fn complicated() {
-    let complicated_fen = "R-0,0,0,0-0,0,0,0-0,0,0,0-0,0,0,0-0-{'lives':(50,50,50,50),'enPassant':('i3:i4','c6:d6','f12:f11','l9:k9')}-
+fn default_matches_default_fen() {
+    let parsed: Board = fen4::DEFAULT_FEN.parse().unwrap();
+    let built = Board::default();
+    assert_eq!(built, parsed);
+    assert_eq!(format!("{:?}", built), format!("{:?}", parsed));
+    assert_eq!(built.to_bytes(), parsed.to_bytes());
+    assert_eq!(built.to_string(), fen4::DEFAULT_FEN);
+}
+
+const COMPLICATED_FEN: &str = "R-0,0,0,0-0,0,0,0-0,0,0,0-0,0,0,0-0-{'lives':(50,50,50,50),'enPassant':('i3:i4','c6:d6','f12:f11','l9:k9')}-
 3,yA,yP,yN,yB,yR,yD,yQ,yK,3/
 3,yE,yH,1,yC,yV,yG,yF,yW,3/
 3,yJ,yL,1,yβ,yα,yY,yS,yI,3/
@@ -40,13 +50,383 @@ bA,bE,bJ,2,rO,rZ,rT,rP,2,gI,gW,gK/
 3,rI,rS,rY,rα,rβ,1,rL,rJ,3/
 3,rW,rF,rG,rV,rC,1,rH,rE,3/
 3,rK,rQ,rD,rR,rB,rN,rP,rA,3";
-    let board: Board = complicated_fen.parse().unwrap();
-    println!("{}", complicated_fen.len());
+
+#[test]
+fn complicated() {
+    let board: Board = COMPLICATED_FEN.parse().unwrap();
+    println!("{}", COMPLICATED_FEN.len());
     let string = board.to_string();
     assert_eq!(
-        complicated_fen, string,
+        COMPLICATED_FEN, string,
         "FromStr and Display are not inverses"
     );
     let board2: Board = string.parse().unwrap();
     assert_eq!(board, board2, "FromStr and Display are not inverses2");
+
+    assert_eq!(board.total_points(), 0);
+    let scored: Board = COMPLICATED_FEN
+        .replacen("0,0,0,0-0-{", "20,3,41,65535-0-{", 1)
+        .parse()
+        .unwrap();
+    assert_eq!(scored.total_points(), 65599);
+    assert_eq!(scored.team_points(fen4::Team::RedYellow), 61);
+    assert_eq!(scored.team_points(fen4::Team::BlueGreen), 65538);
+}
+
+#[test]
+fn newline_separated_rows() {
+    let newline_fen = "R-0,0,0,0-1,1,1,1-1,1,1,1-0,0,0,0-0-
+3,yR,yN,yB,yK,yQ,yB,yN,yR,3
+3,yP,yP,yP,yP,yP,yP,yP,yP,3
+14
+bR,bP,10,gP,gR
+bN,bP,10,gP,gN
+bB,bP,10,gP,gB
+bK,bP,10,gP,gQ
+bQ,bP,10,gP,gK
+bB,bP,10,gP,gB
+bN,bP,10,gP,gN
+bR,bP,10,gP,gR
+14
+3,rP,rP,rP,rP,rP,rP,rP,rP,3
+3,rR,rN,rB,rQ,rK,rB,rN,rR,3";
+    assert!(
+        newline_fen.parse::<Board>().is_err(),
+        "Strict parsing should require '/'"
+    );
+    let board = Board::parse_lenient(newline_fen).unwrap();
+    assert_eq!(board, Board::default());
+    let slash_fen = Board::default().to_string();
+    assert_eq!(Board::parse_lenient(&slash_fen).unwrap(), Board::default());
+}
+
+#[test]
+fn surrounding_whitespace() {
+    let fen = format!("  \n{}\n\n", Board::default());
+    let board: Board = fen.parse().unwrap();
+    assert_eq!(board, Board::default());
+    let crlf = Board::default().to_string().replace("\n", "\r\n") + "\r\n";
+    assert_eq!(crlf.parse::<Board>().unwrap(), Board::default());
+}
+
+#[test]
+fn one_line() {
+    let board = Board::default();
+    let one_line = board.to_fen_oneline();
+    assert!(!one_line.contains('\n'));
+    assert_eq!(one_line, board.to_string().replace("\n", ""));
+    assert_eq!(one_line, format!("{:#}", board));
+    assert_eq!(board, one_line.parse().unwrap());
+}
+
+#[test]
+fn best_effort() {
+    use fen4::{BoardParseError, Piece};
+    let fen = Board::default()
+        .to_string()
+        .replace("rQ", "xQ")
+        .replace("bK,bP,10", "bK,,bP,10");
+    let (board, errors) = Board::parse_best_effort(&fen);
+    assert_eq!(errors.len(), 2, "{:?}", errors);
+    match &errors[0] {
+        BoardParseError::EmptySegment(7, 1) => (),
+        e => panic!("Unexpected first error {:?}", e),
+    }
+    match &errors[1] {
+        BoardParseError::BadSegmentPiece(0, 6, _) => (),
+        e => panic!("Unexpected second error {:?}", e),
+    }
+    assert_eq!(fen.parse::<Board>().unwrap_err(), errors[0]);
+    let mut expected = Board::default();
+    expected.board[0][6] = Piece::Empty;
+    assert_eq!(board, expected);
+
+    let (board, errors) = Board::parse_best_effort(&Board::default().to_string());
+    assert!(errors.is_empty());
+    assert_eq!(board, Board::default());
+}
+
+#[test]
+fn meta_only() {
+    let board =
+        Board::from_meta_str("R-0,0,0,0-1,1,1,1-1,1,1,1-0,0,0,0-0-{'lives':(3,3,3,3)}").unwrap();
+    assert_eq!(board.extra_options.lives, Some([3; 4]));
+    assert!(board.board.iter().flatten().all(|p| p.is_empty()));
+    let with_dash = Board::from_meta_str("R-0,0,0,0-1,1,1,1-1,1,1,1-0,0,0,0-0-").unwrap();
+    let without_dash = Board::from_meta_str("R-0,0,0,0-1,1,1,1-1,1,1,1-0,0,0,0-0").unwrap();
+    assert_eq!(with_dash, without_dash);
+    assert!(Board::from_meta_str("R-0,0,0,0-1,1,1,1").is_err());
+}
+
+#[test]
+fn turn_and_points_only() {
+    use fen4::{MetaDataParseError, TurnColor};
+    let fen = "\nG-0,1,0,0-1,0,1,1-1,1,0,1-5,0,12,3-7-{'lives':(2,2,2,2)}-14/14";
+    assert_eq!(fen4::parse_turn(fen), Ok(TurnColor::Green));
+    assert_eq!(fen4::parse_points(fen), Ok([5, 0, 12, 3]));
+    for board in &[Board::default(), Board::chess960(12)] {
+        let fen = board.to_string();
+        assert_eq!(fen4::parse_turn(&fen), Ok(board.turn));
+        assert_eq!(fen4::parse_points(&fen), Ok(board.points));
+    }
+    assert_eq!(fen4::parse_turn("X-0"), Err(MetaDataParseError::BadColor));
+    assert_eq!(
+        fen4::parse_turn("R"),
+        Err(MetaDataParseError::TooFewSections {
+            needed: 1,
+            found: 0
+        })
+    );
+    assert_eq!(
+        fen4::parse_turn("R").unwrap_err().to_string(),
+        "Expected at least 1 metadata section(s), found 0"
+    );
+    assert_eq!(
+        fen4::parse_points("R-0,0,0,0-1,1,1,1-1,1,1,1-0,0,0,0"),
+        Err(MetaDataParseError::TooFewSections {
+            needed: 5,
+            found: 4
+        })
+    );
+    assert_eq!(
+        fen4::parse_points("R-0,0,0,0-1,1,1,1-1,1,1,1-0,0,0,0,0-0-"),
+        Err(MetaDataParseError::BadComma)
+    );
+}
+
+#[test]
+fn nameable_errors() {
+    use fen4::{BoardParseError, BoardSize, MetaDataParseError};
+    let bad_turn = "X-0,0,0,0-0,0,0,0-0,0,0,0-0,0,0,0-0-14/14/14/14/14/14/14/14/14/14/14/14/14/14";
+    match bad_turn.parse::<Board>() {
+        Err(BoardParseError::BadMetaData(MetaDataParseError::BadColor)) => (),
+        r => panic!("Unexpected result {:?}", r),
+    }
+    let short = "R-0,0,0,0-0,0,0,0-0,0,0,0-0,0,0,0-0-14/14";
+    match short.parse::<Board>() {
+        Err(BoardParseError::BadBoardSize(BoardSize::TooFewRows, 12)) => (),
+        r => panic!("Unexpected result {:?}", r),
+    }
+}
+
+#[test]
+fn error_sources() {
+    use std::error::Error;
+    let bad_royal = "R-0,0,0,0-0,0,0,0-0,0,0,0-0,0,0,0-0-{'royal':('z1','','','')}-14/14/14/14/14/14/14/14/14/14/14/14/14/14";
+    let err = bad_royal.parse::<Board>().unwrap_err();
+    let meta = err.source().expect("metadata error as source");
+    let position = meta.source().expect("position error as source");
+    assert_eq!(
+        position.to_string(),
+        "'z' is not a valid column. Valid columns are 'a'-'n'"
+    );
+    assert!(position.source().is_none());
+
+    let bad_piece =
+        "R-0,0,0,0-0,0,0,0-0,0,0,0-0,0,0,0-0-14/14/14/14/14/14/14/14/14/14/14/14/14/13,zK";
+    let err = bad_piece.parse::<Board>().unwrap_err();
+    assert!(err.source().is_some());
+    let short = "R-0,0,0,0-0,0,0,0-0,0,0,0-0,0,0,0-0-14/14";
+    assert!(short.parse::<Board>().unwrap_err().source().is_none());
+}
+
+#[test]
+fn error_messages() {
+    use fen4::{BoardParseError, BoardSize, MetaDataParseError};
+    assert_eq!(
+        BoardParseError::from(MetaDataParseError::BadColor).to_string(),
+        "Something went wrong with metadata parsing: Only 'R', 'G', 'Y', or 'B' are valid turn colors"
+    );
+    assert_eq!(
+        BoardParseError::BadBoardSize(BoardSize::TooFewColumns, 3).to_string(),
+        "Not enough columns in row 4."
+    );
+    assert_eq!(
+        BoardParseError::BadBoardSize(BoardSize::TooFewRows, 12).to_string(),
+        "12 too few rows overall. Make sure there is not a missing row."
+    );
+    assert_eq!(
+        BoardParseError::EmptySegment(1, 2).to_string(),
+        "Segment at c2 is empty which is not valid."
+    );
+}
+
+#[test]
+fn row_indexed() {
+    let indexed_fen = "R-0,0,0,0-1,1,1,1-1,1,1,1-0,0,0,0-0-
+14: 3,yR,yN,yB,yK,yQ,yB,yN,yR,3/
+13: 3,yP,yP,yP,yP,yP,yP,yP,yP,3/
+12: 14/
+11: bR,bP,10,gP,gR/
+10: bN,bP,10,gP,gN/
+9: bB,bP,10,gP,gB/
+8: bK,bP,10,gP,gQ/
+7: bQ,bP,10,gP,gK/
+6: bB,bP,10,gP,gB/
+5: bN,bP,10,gP,gN/
+4: bR,bP,10,gP,gR/
+3: 14/
+2: 3,rP,rP,rP,rP,rP,rP,rP,rP,3/
+1: 3,rR,rN,rB,rQ,rK,rB,rN,rR,3";
+    assert!(
+        indexed_fen.parse::<Board>().is_err(),
+        "Strict parsing should reject row numbers"
+    );
+    assert_eq!(Board::parse_lenient(indexed_fen).unwrap(), Board::default());
+    let newline_indexed = indexed_fen.replace('/', "");
+    assert_eq!(
+        Board::parse_lenient(&newline_indexed).unwrap(),
+        Board::default()
+    );
+}
+
+#[test]
+// Multi-character shapes are valid pieces with that feature
+#[cfg(not(feature = "multi-char-fairy"))]
+fn lossy() {
+    use fen4::{Color, Piece, TurnColor};
+    let fen = "R-0,0,0,0-1,1,1,1-1,1,1,1-0,0,0,0-0-14/14/14/14/14/14/14/14/14/14/14/14/3,yWizard,drCamel,dHawk,zK,7/3,rR,rN,rB,rQ,rK,rB,rN,rR,3";
+    assert!(fen.parse::<Board>().is_err());
+    let board = Board::from_fen_lossy(fen);
+    assert_eq!(
+        board.board[1][3],
+        Piece::Normal(Color::Turn(TurnColor::Yellow), '?')
+    );
+    assert_eq!(
+        board.board[1][4],
+        Piece::Normal(Color::Dead(Some(TurnColor::Red)), '?')
+    );
+    assert_eq!(board.board[1][5], Piece::Normal(Color::Dead(None), '?'));
+    assert_eq!(board.board[1][6], Piece::Normal(Color::Dead(None), '?'));
+    assert_eq!(board.board[0], Board::default().board[0]);
+}
+
+#[test]
+fn empty_segments() {
+    use fen4::BoardParseError;
+    let fen = Board::default().to_string();
+    let (meta, rows) = fen.split_at(fen.rfind('-').unwrap());
+    let spaced = format!("{}{}", meta, rows.replace(",", ", "));
+    assert_eq!(spaced.parse::<Board>().unwrap(), Board::default());
+    for empty in &[",,", ", ,"] {
+        let fen = Board::default()
+            .to_string()
+            .replace("3,yR,yN", &format!("3,yR{}yN", empty));
+        assert_eq!(
+            fen.parse::<Board>(),
+            Err(BoardParseError::EmptySegment(13, 4))
+        );
+        assert_eq!(Board::parse_lenient(&fen).unwrap(), Board::default());
+    }
+}
+
+#[test]
+fn column_overflow() {
+    use fen4::BoardParseError;
+    let empty = "R-0,0,0,0-0,0,0,0-0,0,0,0-0,0,0,0-0-14/14/14/14/14/14/14/14/14/14/14/14/14/";
+    for (row, segment) in &[("14,yR", 1), ("13,yR,yR", 2), ("15", 0), ("yR,yN,13", 2)] {
+        let fen = format!("{}{}", empty, row);
+        assert_eq!(
+            fen.parse::<Board>(),
+            Err(BoardParseError::ColumnOverflow(0, *segment)),
+            "{}",
+            row
+        );
+    }
+    assert_eq!(
+        BoardParseError::ColumnOverflow(0, 1).to_string(),
+        "Row 1 describes more than 14 columns; segment 1 (counting from 0) goes past the last column."
+    );
+}
+
+#[test]
+fn metadata_line_dashes() {
+    let board = Board::default();
+    let fen = board.to_string();
+    let meta = fen.lines().next().unwrap();
+    assert_eq!(meta, "R-0,0,0,0-1,1,1,1-1,1,1,1-0,0,0,0-0-");
+    assert_eq!(meta.matches('-').count(), 6);
+    assert_eq!(fen.parse::<Board>().unwrap(), board);
+
+    let mut with_extras = Board::default();
+    with_extras.extra_options.lives = Some([3, 3, 3, 3]);
+    let fen = with_extras.to_string();
+    let meta = fen.lines().next().unwrap();
+    assert_eq!(
+        meta,
+        "R-0,0,0,0-1,1,1,1-1,1,1,1-0,0,0,0-0-{'lives':(3,3,3,3)}-"
+    );
+    assert_eq!(meta.matches('-').count(), 7);
+    assert_eq!(fen.parse::<Board>().unwrap(), with_extras);
+
+    // The one line form has the same metadata
+    assert!(with_extras
+        .to_fen_oneline()
+        .starts_with("R-0,0,0,0-1,1,1,1-1,1,1,1-0,0,0,0-0-{'lives':(3,3,3,3)}-3,yR"));
+}
+
+#[test]
+fn piece_counts() {
+    use fen4::TurnColor::*;
+    let board: Board = COMPLICATED_FEN.parse().unwrap();
+    let counts = board.piece_counts();
+    for &color in &[Red, Blue, Yellow, Green] {
+        assert_eq!(counts[&(color, 'K')], 1);
+        assert_eq!(counts[&(color, 'P')], 2);
+        let total: usize = counts
+            .iter()
+            .filter(|((c, _), _)| *c == color)
+            .map(|(_, n)| n)
+            .sum();
+        assert_eq!(total, 29, "{}", color);
+    }
+    // The dead pieces in the middle are left out
+    assert_eq!(counts.get(&(Red, 'α')), Some(&1));
+    assert_eq!(counts.values().sum::<usize>(), 4 * 29);
+    assert_eq!(Board::empty().piece_counts().len(), 0);
+}
+
+#[test]
+fn bad_dash_counts() {
+    use fen4::{BoardParseError, MetaDataParseError};
+    let board = "14/14/14/14/14/14/14/14/14/14/14/14/14/14";
+    let missing = format!("R-0,0,0,0-1,1,1,1-1,1,1,1-0,0,0,0-{}", board);
+    let err = missing.parse::<Board>().unwrap_err();
+    assert_eq!(
+        err,
+        BoardParseError::BadMetaData(MetaDataParseError::BadDash(5))
+    );
+    assert!(err
+        .to_string()
+        .contains("Expected 6 or 7 metadata sections, found 5"));
+
+    let extra = format!(
+        "R-0,0,0,0-1,1,1,1-1,1,1,1-0,0,0,0-0-{{'std2pc':true}}-1-{}",
+        board
+    );
+    assert_eq!(
+        extra.parse::<Board>(),
+        Err(BoardParseError::BadMetaData(MetaDataParseError::BadDash(8)))
+    );
+}
+
+#[test]
+fn try_from() {
+    use fen4::BoardParseError;
+    use std::convert::{TryFrom, TryInto};
+    fn parse_generic<T: for<'a> TryFrom<&'a str>>(s: &str) -> Option<T> {
+        T::try_from(s).ok()
+    }
+    assert_eq!(Board::try_from(fen4::DEFAULT_FEN), Ok(Board::default()));
+    assert_eq!(
+        Board::try_from(COMPLICATED_FEN.to_string()),
+        COMPLICATED_FEN.parse()
+    );
+    assert_eq!(
+        parse_generic::<Board>(fen4::DEFAULT_FEN),
+        Some(Board::default())
+    );
+    let err: Result<Board, BoardParseError> = "R-0,0,0,0".try_into();
+    assert_eq!(err, "R-0,0,0,0".parse::<Board>());
+    assert!(err.is_err());
 }
@@ -0,0 +1,41 @@
+use fen4::Board;
+
+#[test]
+fn round_trip() {
+    let fairy = "G-0,1,0,0-1,0,1,1-1,1,0,1-5,0,12,3-7-{'royal':('h1','','',''),'lives':(2,2,2,2),'newTag':'x'}-
+3,yA,yP,yN,yB,yR,yD,yQ,yK,3/
+3,yE,yH,1,yC,yV,yG,yF,yW,3/
+3,yJ,yL,1,yβ,yα,yY,yS,yI,3/
+bK,bW,bI,2,yP,yT,yZ,yO,2,gJ,gE,gA/
+bQ,bF,bS,3,yδ,yγ,yM,2,gL,gH,gP/
+bD,bG,bY,bO,bM,dK,dQ,dD,dR,1,gP,2,gN/
+bR,bV,bα,bZ,bγ,dB,dN,dP,X,gδ,gT,gβ,gC,gB/
+bB,bC,bβ,bT,bδ,dF,dL,dJ,dT,gγ,gZ,gα,gV,gR/
+bN,2,bP,1,dδ,dγ,dα,dZ,gM,gO,gY,gG,gD/
+bP,bH,bL,2,rM,rγ,rδ,3,gS,gF,gQ/
+bA,bE,bJ,2,rO,rZ,rT,rP,2,gI,gW,gK/
+3,rI,rS,rY,rα,rβ,1,rL,rJ,3/
+X,X,X,rW,rF,rG,rV,rC,1,rH,rE,3/
+X,X,X,rK,rQ,rD,rR,drB,rN,rP,rA,X,X,X";
+    let board: Board = fairy.parse().unwrap();
+    let bytes = board.to_bytes();
+    assert_eq!(bytes[0], 1, "format version");
+    assert_eq!(Board::from_bytes(&bytes), Ok(board));
+
+    for board in &[Board::default(), Board::empty(), Board::chess960(1)] {
+        assert_eq!(Board::from_bytes(&board.to_bytes()).as_ref(), Ok(board));
+    }
+}
+
+#[test]
+fn bad_bytes() {
+    let bytes = Board::default().to_bytes();
+    assert_eq!(Board::from_bytes(&[]), Err(()));
+    assert_eq!(Board::from_bytes(&bytes[..bytes.len() - 1]), Err(()));
+    let mut extended = bytes.clone();
+    extended.push(1);
+    assert_eq!(Board::from_bytes(&extended), Err(()));
+    let mut version = bytes;
+    version[0] = 2;
+    assert_eq!(Board::from_bytes(&version), Err(()));
+}
@@ -0,0 +1,395 @@
+use fen4::{Board, Piece};
+
+#[test]
+fn captured_since_default() {
+    let mut board = Board::default();
+    assert_eq!(
+        board.captured_since_default(),
+        [vec![], vec![], vec![], vec![]]
+    );
+    // Red pawn on h2 takes a blue knight that wandered to g3, then a yellow bishop takes it
+    board.board[2][6] = board.board[1][7].clone();
+    board.board[1][7] = Piece::Empty;
+    board.board[9][0] = Piece::Empty;
+    board.board[2][6] = board.board[13][5].clone();
+    board.board[13][5] = Piece::Empty;
+    assert_eq!(
+        board.captured_since_default(),
+        [vec!['P'], vec!['N'], vec![], vec![]]
+    );
+}
+
+#[test]
+fn same_pieces_ignores_metadata() {
+    let board = Board::default();
+    let mut other = board.clone();
+    other.turn = other.turn.next();
+    other.points = [3, 0, 20, 1];
+    assert_ne!(board, other);
+    assert!(board.same_pieces(&other));
+    other.board[1][3] = Piece::Empty;
+    assert!(!board.same_pieces(&other));
+}
+
+#[test]
+fn material() {
+    use fen4::{Color, TurnColor};
+    let mut board = Board::default();
+    assert_eq!(board.material(), [39; 4]);
+    // Blue loses a queen and Green's pieces are all dead
+    board.board[6][0] = Piece::Empty;
+    for row in board.board.iter_mut() {
+        for piece in row.iter_mut() {
+            if let Piece::Normal(Color::Turn(TurnColor::Green), shape) = piece {
+                *piece = Piece::Normal(Color::Dead(Some(TurnColor::Green)), *shape);
+            }
+        }
+    }
+    assert_eq!(board.material(), [39, 30, 39, 0]);
+    assert_eq!(board.material_with(|_| 1), [16, 15, 16, 0]);
+}
+
+#[test]
+fn with_piece_chain() {
+    use fen4::{Color, Position, TurnColor};
+    let queen = Piece::Normal(Color::Turn(TurnColor::Red), 'Q');
+    let board = Board::default()
+        .with_piece(Position { row: 0, col: 6 }, Piece::Empty)
+        .with_piece(Position { row: 5, col: 6 }, queen.clone())
+        .with_piece(Position { row: 12, col: 6 }, Piece::Empty);
+    assert_eq!(board.board[0][6], Piece::Empty);
+    assert_eq!(board.board[5][6], queen);
+    assert_eq!(board.board[12][6], Piece::Empty);
+    assert_eq!(board.board[12][7], Board::default().board[12][7]);
+}
+
+#[test]
+#[should_panic]
+fn with_piece_off_board() {
+    Board::default().with_piece(fen4::Position { row: 14, col: 0 }, Piece::Wall);
+}
+
+#[test]
+fn eq_ignoring_turn() {
+    let board = Board::default();
+    let mut other = board.clone();
+    other.turn = other.turn.prev();
+    assert_ne!(board, other);
+    assert!(board.eq_ignoring_turn(&other));
+    other.draw_ply = 1;
+    assert!(!board.eq_ignoring_turn(&other));
+}
+
+#[test]
+fn flat_round_trip() {
+    let board = Board::chess960(12);
+    let flat = board.to_flat();
+    assert_eq!(flat.len(), 196);
+    assert_eq!(flat[14 + 5], board.board[1][5]);
+    assert_eq!(Board::from_flat(&flat), Ok(board));
+    assert_eq!(Board::from_flat(&flat[1..]), Err(()));
+}
+
+#[test]
+fn game_mode() {
+    use fen4::GameMode;
+    let mut board = Board::default();
+    assert_eq!(board.game_mode(), GameMode::Unknown);
+    board.board[1][4] = Piece::Empty;
+    assert_eq!(board.game_mode(), GameMode::Teams);
+    board.points[2] = 1;
+    assert_eq!(board.game_mode(), GameMode::FreeForAll);
+    let mut board = Board::default();
+    board.dead[3] = true;
+    assert_eq!(board.game_mode(), GameMode::FreeForAll);
+}
+
+#[test]
+fn find_pieces() {
+    use fen4::{Color, Position, TurnColor};
+    let board = Board::default();
+    let green_rook = Piece::Normal(Color::Turn(TurnColor::Green), 'R');
+    assert_eq!(
+        board.find(&green_rook),
+        vec![Position { row: 3, col: 13 }, Position { row: 10, col: 13 }]
+    );
+    assert_eq!(
+        board.find_first(&green_rook),
+        Some(Position { row: 3, col: 13 })
+    );
+    assert_eq!(board.find(&Piece::Empty).len(), 196 - 64);
+    let dead_king = Piece::Normal(Color::Dead(None), 'K');
+    assert!(board.find(&dead_king).is_empty());
+    assert_eq!(board.find_first(&dead_king), None);
+}
+
+#[test]
+fn iter_shape_of() {
+    use fen4::{Position, TurnColor};
+    let board = Board::default();
+    let pawns: Vec<Position> = board.iter_shape_of(TurnColor::Red, 'P').collect();
+    assert_eq!(pawns.len(), 8);
+    assert!(pawns.iter().all(|p| p.row == 1));
+    let rooks: Vec<Position> = board.iter_shape_of(TurnColor::Blue, 'R').collect();
+    assert_eq!(
+        rooks,
+        vec![Position { row: 3, col: 0 }, Position { row: 10, col: 0 }]
+    );
+    assert_eq!(board.iter_shape_of(TurnColor::Yellow, 'α').count(), 0);
+}
+
+#[test]
+fn without_extras() {
+    let fen = "Y-0,0,0,0-1,1,1,1-1,1,1,1-0,0,0,0-3-{'lives':(2,2,2,2),'pawnsBaseRank':3}-14/14/14/14/14/14/14/14/14/14/14/14/14/14";
+    let board: Board = fen.parse().unwrap();
+    let stripped = board.without_extras();
+    assert_eq!(stripped.extra_options, Default::default());
+    assert_eq!(stripped.turn, board.turn);
+    assert_eq!(stripped.draw_ply, 3);
+    assert!(stripped.same_pieces(&board));
+}
+
+#[test]
+fn empty_boards() {
+    let empty = Board::empty();
+    assert!(empty.same_pieces(&Board {
+        board: Default::default(),
+        ..Board::default()
+    }));
+    assert_eq!(empty.turn, Board::default().turn);
+    assert_eq!(empty.castling_king, Board::default().castling_king);
+    assert_eq!(empty.to_string().parse::<Board>().unwrap(), empty);
+
+    let walled = Board::empty_with_walls();
+    assert_eq!(walled.to_string().parse::<Board>().unwrap(), walled);
+    assert!(format!("{:#}", walled).ends_with("14/14/X,X,X,8,X,X,X/X,X,X,8,X,X,X/X,X,X,8,X,X,X"));
+    assert_eq!(walled.find(&fen4::Piece::Wall).len(), 36);
+}
+
+#[test]
+fn ascii_round_trip() {
+    use fen4::{BoardParseError, BoardSize, Color, TurnColor};
+    let mut board = Board::empty_with_walls();
+    board.board[0][7] = Piece::Normal(Color::Turn(TurnColor::Red), 'K');
+    board.board[5][5] = Piece::Normal(Color::Dead(None), 'Q');
+    board.board[6][6] = Piece::Normal(Color::Dead(Some(TurnColor::Green)), 'α');
+    let ascii = board.to_ascii();
+    let lines: Vec<&str> = ascii.lines().collect();
+    assert_eq!(lines[7], ".. .. .. .. .. .. Gα .. .. .. .. .. .. ..");
+    assert_eq!(lines[8], ".. .. .. .. .. dQ .. .. .. .. .. .. .. ..");
+    assert_eq!(lines[13], "XX XX XX .. .. .. .. rK .. .. .. XX XX XX");
+    assert_eq!(Board::from_ascii(&ascii), Ok(board));
+
+    let mut resigned = Board::default();
+    resigned.resign(TurnColor::Blue);
+    let from_ascii = Board::from_ascii(&resigned.to_ascii()).unwrap();
+    assert!(from_ascii.same_pieces(&resigned));
+
+    assert_eq!(
+        Board::from_ascii(&lines[1..].join("\n")),
+        Err(BoardParseError::BadBoardSize(BoardSize::TooFewRows, 1))
+    );
+    assert!(matches!(
+        Board::from_ascii(&ascii.replacen("XX", "zK", 1)),
+        Err(BoardParseError::BadSegmentPiece(13, 0, _))
+    ));
+}
+
+#[test]
+fn board_size() {
+    use fen4::BOARD_SIZE;
+    let board = Board::default();
+    assert_eq!(board.board.len(), BOARD_SIZE);
+    assert!(board.board.iter().all(|row| row.len() == BOARD_SIZE));
+    assert_eq!(board.to_flat().len(), BOARD_SIZE * BOARD_SIZE);
+}
+
+#[test]
+fn same_position() {
+    let board = Board::default();
+    let mut other = board.clone();
+    other.points = [1, 2, 3, 4];
+    other.draw_ply = 9;
+    other.dead[3] = true;
+    other.extra_options.lives = Some([1; 4]);
+    assert!(board.same_position(&other));
+
+    let mut turn = board.clone();
+    turn.turn = turn.turn.next();
+    assert!(!board.same_position(&turn));
+    let mut castling = board.clone();
+    castling.castling_queen[2] = false;
+    assert!(!board.same_position(&castling));
+    let moved = board.with_piece((1, 3).into(), Piece::Empty);
+    assert!(!board.same_position(&moved));
+}
+
+#[test]
+fn collect_pieces() {
+    use fen4::{Color, Position, TurnColor};
+    let board = Board::default();
+    let rebuilt: Board = board.pieces().collect();
+    assert_eq!(rebuilt, board);
+    assert_eq!(board.pieces().count(), 64);
+
+    let a1 = Position { row: 0, col: 0 };
+    let queen = Piece::Normal(Color::Turn(TurnColor::Blue), 'Q');
+    let board: Board = vec![
+        (a1, Piece::Wall),
+        (Position { row: 5, col: 5 }, queen.clone()),
+        (a1, queen.clone()),
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(board.board[0][0], queen);
+    assert_eq!(board.pieces().count(), 2);
+    assert!(board.eq_ignoring_turn(
+        &Board::empty()
+            .with_piece(a1, queen.clone())
+            .with_piece(Position { row: 5, col: 5 }, queen)
+    ));
+}
+
+#[test]
+fn standard_fen_2pc() {
+    use fen4::{Color, ConversionError, Position, TurnColor};
+    let red = |shape| Piece::Normal(Color::Turn(TurnColor::Red), shape);
+    let yellow = |shape| Piece::Normal(Color::Turn(TurnColor::Yellow), shape);
+    let mut board: Board = (3..11)
+        .zip("RNBQKBNR".chars())
+        .flat_map(|(col, shape)| {
+            vec![
+                (Position { row: 0, col }, red(shape)),
+                (Position { row: 1, col }, red('P')),
+                (Position { row: 6, col }, yellow('P')),
+                (Position { row: 7, col }, yellow(shape)),
+            ]
+        })
+        .collect();
+    assert_eq!(board.to_standard_fen_2pc(), Err(ConversionError::NotStd2pc));
+
+    board.extra_options.std2pc = true;
+    board.castling_king = [true, false, true, false];
+    board.castling_queen = [true, false, true, false];
+    assert_eq!(
+        board.to_standard_fen_2pc().unwrap(),
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"
+    );
+
+    board.turn = TurnColor::Blue;
+    assert_eq!(
+        board.to_standard_fen_2pc(),
+        Err(ConversionError::UnsupportedTurn(TurnColor::Blue))
+    );
+    board.castling_queen = [false; 4];
+    board
+        .make_move(Position { row: 1, col: 7 }, Position { row: 3, col: 7 })
+        .unwrap();
+    board.turn = TurnColor::Yellow;
+    board.extra_options.enpassant[0] =
+        Some((Position { row: 2, col: 7 }, Position { row: 3, col: 7 }));
+    board.draw_ply = 0;
+    assert_eq!(
+        board.to_standard_fen_2pc().unwrap(),
+        "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b Kk e3 0 1"
+    );
+
+    let outside = Position { row: 8, col: 7 };
+    let moved = board.clone().with_piece(outside, yellow('Q'));
+    assert_eq!(
+        moved.to_standard_fen_2pc(),
+        Err(ConversionError::OutsideArea(outside))
+    );
+    let blue = Position { row: 4, col: 4 };
+    let moved = board
+        .clone()
+        .with_piece(blue, Piece::Normal(Color::Turn(TurnColor::Blue), 'Q'));
+    assert_eq!(
+        moved.to_standard_fen_2pc(),
+        Err(ConversionError::UnsupportedPiece(blue))
+    );
+
+    // Walls blocking off the unused squares are left out, like validate_std2pc allows
+    let mut walled = board.clone();
+    for pos in Board::playable_positions().filter(|pos| pos.row >= 8 || pos.col < 3 || pos.col > 10)
+    {
+        walled.board[pos.row][pos.col] = Piece::Wall;
+    }
+    walled.board[0][0] = Piece::Wall;
+    walled.board[13][13] = Piece::Wall;
+    assert_eq!(walled.validate_std2pc(), Ok(()));
+    assert_eq!(walled.to_standard_fen_2pc(), board.to_standard_fen_2pc());
+    let inside = Position { row: 4, col: 4 };
+    assert_eq!(
+        walled.with_piece(inside, Piece::Wall).to_standard_fen_2pc(),
+        Err(ConversionError::UnsupportedPiece(inside))
+    );
+
+    // Castling rights are only written while the king and that rook are home
+    let mut rights = board;
+    rights.castling_queen = [true; 4];
+    assert!(rights.to_standard_fen_2pc().unwrap().contains(" b KQkq "));
+    let rook_moved = rights
+        .clone()
+        .with_piece(Position { row: 0, col: 3 }, Piece::Empty)
+        .with_piece(Position { row: 7, col: 10 }, Piece::Empty);
+    assert!(rook_moved.to_standard_fen_2pc().unwrap().contains(" b Kq "));
+    let king_moved = rights.with_piece(Position { row: 7, col: 7 }, Piece::Empty);
+    assert!(king_moved.to_standard_fen_2pc().unwrap().contains(" b KQ "));
+}
+
+#[test]
+fn dead_and_living_pieces() {
+    use fen4::{Color, TurnColor};
+    let board: Board = "R-0,0,0,0-0,0,0,0-0,0,0,0-0,0,0,0-0-14/14/14/14/14/14/14/3,dK,drQ,dgP,X,rK,yP,dS,4/14/14/14/14/14/14"
+        .parse()
+        .unwrap();
+    let dead = |color, shape| Piece::Normal(Color::Dead(color), shape);
+    assert_eq!(
+        board.dead_pieces(),
+        vec![
+            dead(None, 'K'),
+            dead(Some(TurnColor::Red), 'Q'),
+            dead(Some(TurnColor::Green), 'P'),
+            dead(None, 'S'),
+        ]
+    );
+    assert_eq!(
+        board.living_pieces(TurnColor::Red),
+        vec![Piece::Normal(Color::Turn(TurnColor::Red), 'K')]
+    );
+    assert_eq!(
+        board.living_pieces(TurnColor::Yellow),
+        vec![Piece::Normal(Color::Turn(TurnColor::Yellow), 'P')]
+    );
+    assert!(board.living_pieces(TurnColor::Green).is_empty());
+    assert!(Board::default().dead_pieces().is_empty());
+    assert_eq!(Board::default().living_pieces(TurnColor::Blue).len(), 16);
+}
+
+#[test]
+fn rows_in_order() {
+    use fen4::{Color, TurnColor};
+    let board = Board::default();
+    let top_down: Vec<_> = board.rows_top_down().collect();
+    let bottom_up: Vec<_> = board.rows_bottom_up().collect();
+    assert_eq!(top_down.len(), 14);
+    assert_eq!(bottom_up.len(), 14);
+    assert_eq!(
+        top_down[13][7],
+        Piece::Normal(Color::Turn(TurnColor::Red), 'K')
+    );
+    assert_eq!(
+        bottom_up[0][7],
+        Piece::Normal(Color::Turn(TurnColor::Red), 'K')
+    );
+    for (a, b) in top_down.iter().zip(bottom_up.iter().rev()) {
+        assert_eq!(a, b);
+    }
+
+    // The first line of the fen4 board is the first row from the top
+    let fen = board.to_string();
+    let first_line = fen.lines().nth(1).unwrap();
+    assert!(first_line.starts_with("3,yR"));
+    assert_eq!(top_down[0][3].to_string(), "yR");
+}
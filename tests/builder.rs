@@ -0,0 +1,35 @@
+use fen4::{Board, BoardBuilder, Color, Extra, Piece, Position, TurnColor};
+
+#[test]
+fn build_position() {
+    let red_king = Piece::Normal(Color::Turn(TurnColor::Red), 'K');
+    let blue_queen = Piece::Normal(Color::Turn(TurnColor::Blue), 'Q');
+    let board = BoardBuilder::new()
+        .turn(TurnColor::Blue)
+        .piece(Position { row: 0, col: 7 }, red_king)
+        .piece(Position { row: 6, col: 0 }, blue_queen)
+        .dead([false, false, true, true])
+        .castling_king([true, false, false, false])
+        .castling_queen([false; 4])
+        .points([1, 2, 3, 4])
+        .draw_ply(9)
+        .build();
+    let fen =
+        "B-0,0,1,1-1,0,0,0-0,0,0,0-1,2,3,4-9-14/14/14/14/14/14/14/bQ,13/14/14/14/14/14/7,rK,6";
+    assert_eq!(board, fen.parse::<Board>().unwrap());
+}
+
+#[test]
+fn build_from_board() {
+    let extra = Extra {
+        lives: Some([3; 4]),
+        ..Extra::default()
+    };
+    let board = BoardBuilder::from(Board::default())
+        .piece(Position { row: 1, col: 3 }, Piece::Empty)
+        .extra_options(extra.clone())
+        .build();
+    assert_eq!(board.board[1][3], Piece::Empty);
+    assert_eq!(board.board[1][4], Board::default().board[1][4]);
+    assert_eq!(board.extra_options, extra);
+}
@@ -0,0 +1,68 @@
+use fen4::{Board, Color, MoveCache, Piece, Position, TurnColor};
+
+#[test]
+fn same_as_legal_moves() {
+    let mut cache = MoveCache::new();
+    let mut board = Board::default();
+    for color in TurnColor::iter() {
+        let expected = board.legal_moves(color);
+        assert_eq!(cache.cached_legal_moves(&board, color), &expected[..]);
+    }
+    assert_eq!(cache.len(), 4);
+
+    // Changing the board gives fresh results rather than stale ones
+    board
+        .make_move(Position { row: 1, col: 7 }, Position { row: 3, col: 7 })
+        .unwrap();
+    let expected = board.legal_moves(TurnColor::Red);
+    assert_eq!(
+        cache.cached_legal_moves(&board, TurnColor::Red),
+        &expected[..]
+    );
+    assert_eq!(cache.len(), 5);
+    cache.clear();
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn same_position_different_tags() {
+    let mut cache = MoveCache::new();
+    let board = Board::default();
+    let mut raised = board.clone();
+    raised.extra_options.pawnbaserank = 3;
+    assert_eq!(board.position_hash(), raised.position_hash());
+    assert_eq!(cache.cached_legal_moves(&board, TurnColor::Red).len(), 20);
+    // Red's pawns can't jump from rank 2 anymore, so the stored moves don't apply
+    assert_eq!(
+        cache.cached_legal_moves(&raised, TurnColor::Red),
+        &raised.legal_moves(TurnColor::Red)[..]
+    );
+    assert_eq!(cache.cached_legal_moves(&raised, TurnColor::Red).len(), 12);
+    assert_eq!(cache.len(), 1);
+
+    // Metadata the moves don't depend on shares the entry
+    let scored = Board {
+        points: [5, 0, 0, 0],
+        ..raised
+    };
+    assert_eq!(cache.cached_legal_moves(&scored, TurnColor::Red).len(), 12);
+    assert_eq!(cache.len(), 1);
+}
+
+#[test]
+fn repeated_position() {
+    let board = Board::default().with_piece(
+        Position { row: 6, col: 6 },
+        Piece::Normal(Color::Turn(TurnColor::Green), 'Q'),
+    );
+    let expected = board.legal_moves(TurnColor::Green);
+    let mut cache = MoveCache::new();
+    for _ in 0..10_000 {
+        assert_eq!(
+            cache.cached_legal_moves(&board, TurnColor::Green),
+            &expected[..]
+        );
+    }
+    // Only the first lookup generated moves
+    assert_eq!(cache.len(), 1);
+}
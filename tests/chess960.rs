@@ -30,3 +30,61 @@ bB,bP,10,gP,gR/
     println!("{} \n vs \n{} ", board, board960);
     assert_eq!(board, board960);
 }
+
+#[test]
+fn number_round_trip() {
+    for n in 1..=960 {
+        assert_eq!(Board::chess960(n).chess960_number(), Some(n));
+    }
+}
+
+#[test]
+fn not_960() {
+    use fen4::{Color, Piece, TurnColor};
+    let mut bishops_same_color = Board::default();
+    bishops_same_color.board[0].swap(5, 4);
+    bishops_same_color.board[0].swap(4, 10);
+    assert_eq!(bishops_same_color.chess960_number(), None);
+    let mut dead_rook = Board::default();
+    dead_rook.board[0][3] = Piece::Normal(Color::Dead(None), 'R');
+    assert_eq!(dead_rook.chess960_number(), None);
+    let mut two_queens = Board::default();
+    two_queens.board[0][4] = Piece::Normal(Color::Turn(TurnColor::Red), 'Q');
+    assert_eq!(two_queens.chess960_number(), None);
+}
+
+#[test]
+fn back_rank_in_every_direction() {
+    use fen4::{Color, Piece, TurnColor};
+    assert_eq!(
+        Board::chess960_back_rank(1),
+        ['B', 'B', 'Q', 'N', 'N', 'R', 'K', 'R']
+    );
+    assert_eq!(
+        Board::chess960_back_rank(960),
+        ['R', 'K', 'R', 'N', 'N', 'Q', 'B', 'B']
+    );
+    for &n in &[1, 2, 100, 333, 777, 960] {
+        let rank = Board::chess960_back_rank(n);
+        let board = Board::chess960(n);
+        for (i, &shape) in rank.iter().enumerate() {
+            // Laid out like the default position: Red and Blue count up from d1 and a4, Yellow and
+            // Green count down from k14 and n11
+            let squares = [
+                (TurnColor::Red, 0, i + 3),
+                (TurnColor::Blue, i + 3, 0),
+                (TurnColor::Yellow, 13, 10 - i),
+                (TurnColor::Green, 10 - i, 13),
+            ];
+            for &(color, row, col) in &squares {
+                assert_eq!(
+                    board.board[row][col],
+                    Piece::Normal(Color::Turn(color), shape),
+                    "{:?} has the wrong piece for {}",
+                    color,
+                    n
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use fen4::{Board, BoardDelta, Piece};
+
+#[test]
+fn delta_round_trip() {
+    let a = Board::default();
+    let mut b = a.clone();
+    b.board[3][7] = b.board[1][7].clone();
+    b.board[1][7] = Piece::Empty;
+    b.turn = b.turn.next();
+    let delta = a.delta_to(&b);
+    assert_eq!(delta.squares().len(), 2);
+    let mut applied = a.clone();
+    applied.apply_delta(&delta);
+    assert_eq!(applied, b);
+
+    let compact = delta.to_string();
+    assert_eq!(compact, "B-0,0,0,0-1,1,1,1-1,1,1,1-0,0,0,0-0-|h2=,h4=rP");
+    let parsed: BoardDelta = compact.parse().unwrap();
+    assert_eq!(parsed, delta);
+
+    let mut applied = b.clone();
+    applied.apply_delta(&b.delta_to(&a));
+    assert_eq!(applied, a);
+}
+
+#[test]
+fn delta_metadata_only() {
+    let a = Board::default();
+    let mut b = a.clone();
+    b.extra_options.lives = Some([1, 2, 3, 4]);
+    let delta = a.delta_to(&b);
+    assert!(delta.squares().is_empty());
+    let parsed: BoardDelta = delta.to_string().parse().unwrap();
+    let mut applied = a.clone();
+    applied.apply_delta(&parsed);
+    assert_eq!(applied, b);
+    assert!(a.delta_to(&a).is_empty());
+    assert_eq!(a.delta_to(&a).to_string(), "|");
+    assert!("h4=rP".parse::<BoardDelta>().is_err());
+}
@@ -0,0 +1,256 @@
+use fen4::{Board, ExtraValue};
+use std::collections::HashSet;
+
+#[test]
+fn set_extra_lives() {
+    let mut board = Board::default();
+    let lives = ExtraValue::Array(vec![
+        ExtraValue::Number(3),
+        ExtraValue::Number(2),
+        ExtraValue::Number(1),
+        ExtraValue::Number(0),
+    ]);
+    board.set_extra("lives", lives).unwrap();
+    assert_eq!(board.extra_options.lives, Some([3, 2, 1, 0]));
+    // Setting a tag again replaces the old value instead of being a repeat
+    let lives = ExtraValue::Array(vec![ExtraValue::Number(9); 4]);
+    board.set_extra("lives", lives).unwrap();
+    assert_eq!(board.extra_options.lives, Some([9; 4]));
+}
+
+#[test]
+fn set_extra_pawns_base_rank() {
+    let mut board = Board::default();
+    board
+        .set_extra("pawnsBaseRank", ExtraValue::Number(0))
+        .unwrap();
+    assert_eq!(board.extra_options.pawnbaserank, 0);
+    let before = board.clone();
+    assert!(board
+        .set_extra("pawnsBaseRank", ExtraValue::Boolean(true))
+        .is_err());
+    assert!(board.set_extra("notATag", ExtraValue::Number(1)).is_err());
+    assert_eq!(board, before);
+}
+
+#[test]
+fn unknown_tags_round_trip() {
+    let fen = "R-0,0,0,0-1,1,1,1-1,1,1,1-0,0,0,0-0-{'newTag':(1,2,3,4),'lives':(3,3,3,3),'other':'x'}-14/14/14/14/14/14/14/14/14/14/14/14/14/14";
+    let board: Board = fen.parse().unwrap();
+    assert_eq!(board.extra_options.lives, Some([3; 4]));
+    assert_eq!(
+        board.extra_options.unknown,
+        vec![
+            ("newTag".to_string(), "(1,2,3,4)".to_string()),
+            ("other".to_string(), "'x'".to_string()),
+        ]
+    );
+    let written = format!("{:#}", board);
+    assert_eq!(written, fen);
+    assert_eq!(written.parse::<Board>().unwrap(), board);
+
+    // Unknown tags stay after the recognized tag they followed even when those are reordered
+    let reordered = "R-0,0,0,0-1,1,1,1-1,1,1,1-0,0,0,0-0-{'pawnsBaseRank':3,'newTag':1,'lives':(3,3,3,3)}-14/14/14/14/14/14/14/14/14/14/14/14/14/14";
+    let board: Board = reordered.parse().unwrap();
+    assert!(format!("{:#}", board).contains("{'lives':(3,3,3,3),'pawnsBaseRank':3,'newTag':1}"));
+    let mut pushed = board.clone();
+    pushed
+        .extra_options
+        .unknown
+        .push(("added".to_string(), "2".to_string()));
+    assert!(format!("{:#}", pushed).contains("'pawnsBaseRank':3,'newTag':1,'added':2}"));
+}
+
+#[test]
+fn uniquify_and_std2pc() {
+    let fen = "R-0,0,0,0-1,1,1,1-1,1,1,1-0,0,0,0-0-{'uniquify':5,'std2pc':true}-14/14/14/14/14/14/14/14/14/14/14/14/14/14";
+    let board: Board = fen.parse().unwrap();
+    assert_eq!(format!("{:#}", board), fen);
+}
+
+#[test]
+fn preserve_tag_order() {
+    let fen = "R-0,0,0,0-1,1,1,1-1,1,1,1-0,0,0,0-0-{'enPassant':('','','',''),'newTag':1,'kingSquares':('h1','','',''),'lives':(3,3,3,3)}-14/14/14/14/14/14/14/14/14/14/14/14/14/14";
+    let board: Board = fen.parse().unwrap();
+    let preserved = board.to_string_preserving_order().replace('\n', "");
+    // enPassant is all empty so it is left out, and kingSquares is written as royal
+    assert!(preserved.starts_with(
+        "R-0,0,0,0-1,1,1,1-1,1,1,1-0,0,0,0-0-{'newTag':1,'royal':('h1','','',''),'lives':(3,3,3,3)}-"
+    ));
+    assert_eq!(preserved.parse::<Board>().unwrap(), board);
+    // Boards with their tags in another order are equal and hash the same, but their orders differ
+    let canonical: Board = format!("{:#}", board).parse().unwrap();
+    assert_eq!(canonical, board);
+    assert_eq!(HashSet::from([canonical.clone(), board.clone()]).len(), 1);
+    assert_ne!(
+        canonical.extra_options.tag_order,
+        board.extra_options.tag_order
+    );
+
+    let mut with_flag = board.clone();
+    with_flag.extra_options.std2pc = true;
+    let preserved = with_flag.to_string_preserving_order();
+    assert!(preserved.contains("'lives':(3,3,3,3),'std2pc':true}"));
+
+    // Tags set by name keep the order they were set in, after the parsed tags
+    let mut set = board.clone();
+    set.set_extra("uniquify", ExtraValue::Number(4)).unwrap();
+    set.set_extra("pawnsBaseRank", ExtraValue::Number(3))
+        .unwrap();
+    set.set_extra(
+        "kingSquares",
+        ExtraValue::Array(vec![
+            ExtraValue::String("h1".into()),
+            ExtraValue::String(String::new()),
+            ExtraValue::String(String::new()),
+            ExtraValue::String(String::new()),
+        ]),
+    )
+    .unwrap();
+    let labels: Vec<&str> = set.extra_options.tag_order.labels().collect();
+    assert_eq!(
+        labels,
+        [
+            "enPassant",
+            "newTag",
+            "royal",
+            "lives",
+            "uniquify",
+            "pawnsBaseRank"
+        ]
+    );
+    assert!(set
+        .to_string_preserving_order()
+        .contains("'lives':(3,3,3,3),'uniquify':4,'pawnsBaseRank':3}"));
+    // The order is only for presentation
+    let reordered: Board = format!("{:#}", board).parse().unwrap();
+    assert_eq!(reordered, board);
+}
+
+#[test]
+fn checks() {
+    let fen = "R-0,0,0,0-1,1,1,1-1,1,1,1-0,0,0,0-0-{'checks':(0,2,1,0),'lives':(3,3,3,3)}-14/14/14/14/14/14/14/14/14/14/14/14/14/14";
+    let mut board: Board = fen.parse().unwrap();
+    assert_eq!(board.extra_options.checks, Some([0, 2, 1, 0]));
+    assert!(format!("{:#}", board).starts_with(
+        "R-0,0,0,0-1,1,1,1-1,1,1,1-0,0,0,0-0-{'lives':(3,3,3,3),'checks':(0,2,1,0)}-"
+    ));
+    let checks = ExtraValue::Array(vec![ExtraValue::Number(1); 4]);
+    board.set_extra(fen4::CHECKS_TAG, checks).unwrap();
+    assert_eq!(board.extra_options.checks, Some([1; 4]));
+    let repeated = fen.replace("'lives'", "'checks'");
+    assert!(repeated.parse::<Board>().is_err());
+}
+
+#[test]
+fn all_tags_in_preferred_order() {
+    let extra = "{'royal':('h1','a7','g14','n8'),'lives':(3,3,3,3),'checks':(0,2,1,0),\
+        'resigned':(false,true,false,false),'flagged':(false,false,true,false),\
+        'stalemated':(false,false,false,true),'gameOver':'Red wins',\
+        'zombieImmune':(true,false,false,false),'zombieType':('','muncher','',''),\
+        'enPassant':('i3:i4','','',''),'pawnsBaseRank':3,'uniquify':42,'std2pc':true,\
+        'eliminated':(0,1,0,0)}";
+    let fen = format!(
+        "R-0,0,0,0-1,1,1,1-1,1,1,1-0,0,0,0-0-{}-14/14/14/14/14/14/14/14/14/14/14/14/14/14",
+        extra
+    );
+    let board: Board = fen.parse().unwrap();
+    assert_eq!(board.extra_options.game_over, "Red wins");
+    assert_eq!(board.extra_options.to_string(), &extra[1..extra.len() - 1]);
+    assert_eq!(format!("{:#}", board), fen);
+
+    // Tags given in another order come back out in the preferred order
+    let shuffled = fen.replace(
+        "'stalemated':(false,false,false,true),'gameOver':'Red wins',",
+        "",
+    );
+    let shuffled = shuffled.replace(
+        "'std2pc':true",
+        "'std2pc':true,'gameOver':'Red wins','stalemated':(false,false,false,true)",
+    );
+    let reordered: Board = shuffled.parse().unwrap();
+    assert_eq!(reordered, board);
+    assert_eq!(format!("{:#}", reordered), fen);
+    assert_eq!(
+        reordered.to_string_preserving_order().replace('\n', ""),
+        shuffled
+    );
+}
+
+#[test]
+fn null_booleans() {
+    let fen = "R-0,0,0,0-1,1,1,1-1,1,1,1-0,0,0,0-0-{'resigned':(null,true,false,null),'zombieImmune':(true,null,null,null)}-14/14/14/14/14/14/14/14/14/14/14/14/14/14";
+    let board: Board = fen.parse().unwrap();
+    assert_eq!(board.extra_options.resigned, [false, true, false, false]);
+    assert!(board.extra_options.nulls.is_null("resigned", 0));
+    assert!(!board.extra_options.nulls.is_null("resigned", 2));
+    assert_eq!(board.to_string_preserving_order().replace('\n', ""), fen);
+    // Display writes the canonical form and nulls don't affect equality
+    let canonical = format!("{:#}", board);
+    assert!(canonical.contains("'resigned':(false,true,false,false)"));
+    let reparsed: Board = canonical.parse().unwrap();
+    assert_eq!(reparsed, board);
+    assert_ne!(reparsed.extra_options.nulls, board.extra_options.nulls);
+
+    // Slots that have become true are written as true
+    let mut resigned = board.clone();
+    resigned.resign(fen4::TurnColor::Red);
+    assert!(resigned
+        .to_string_preserving_order()
+        .contains("'resigned':(true,true,false,null)"));
+    // Replacing a tag forgets its nulls
+    let mut replaced = board;
+    replaced
+        .set_extra(
+            "zombieImmune",
+            ExtraValue::Array(vec![ExtraValue::Boolean(true); 4]),
+        )
+        .unwrap();
+    assert!(replaced
+        .to_string_preserving_order()
+        .contains("'zombieImmune':(true,true,true,true)"));
+
+    // Tags that are all false are still written when some of their slots were null
+    for tags in &[
+        "{'resigned':(null,null,null,null)}",
+        "{'resigned':(null,false,null,null)}",
+        "{'flagged':(false,null,false,false),'lives':(2,2,2,2)}",
+    ] {
+        let fen = format!(
+            "R-0,0,0,0-1,1,1,1-1,1,1,1-0,0,0,0-0-{}-14/14/14/14/14/14/14/14/14/14/14/14/14/14",
+            tags
+        );
+        let board: Board = fen.parse().unwrap();
+        assert_eq!(board.to_string_preserving_order().replace('\n', ""), fen);
+    }
+    let all_null: Board = "R-0,0,0,0-1,1,1,1-1,1,1,1-0,0,0,0-0-{'resigned':(null,null,null,null)}-14/14/14/14/14/14/14/14/14/14/14/14/14/14"
+        .parse()
+        .unwrap();
+    assert_eq!(all_null, Board::empty());
+    assert_eq!(all_null.to_string(), Board::empty().to_string());
+}
+
+#[test]
+fn eliminated() {
+    use fen4::{TurnColor, ELIMINATED_TAG};
+    let fen = "R-0,0,0,0-1,1,1,1-1,1,1,1-0,0,0,0-0-{'eliminated':(2,0,1,3)}-14/14/14/14/14/14/14/14/14/14/14/14/14/14";
+    let mut board: Board = fen.parse().unwrap();
+    assert_eq!(board.extra_options.eliminated, [2, 0, 1, 3]);
+    assert_eq!(
+        board.extra_options.elimination_order(),
+        vec![TurnColor::Yellow, TurnColor::Red, TurnColor::Green]
+    );
+    assert_eq!(board.to_string().replace('\n', ""), fen);
+
+    let order = ExtraValue::Array(vec![ExtraValue::Number(0); 4]);
+    board.set_extra(ELIMINATED_TAG, order).unwrap();
+    assert!(board.extra_options.elimination_order().is_empty());
+    let plain: Board = fen
+        .replace("{'eliminated':(2,0,1,3)}-", "")
+        .parse()
+        .unwrap();
+    assert_eq!(board, plain);
+
+    let repeated = fen.replace("(2,0,1,3)}", "(2,0,1,3),'eliminated':(1,0,0,0)}");
+    assert!(repeated.parse::<Board>().is_err());
+}
@@ -0,0 +1,188 @@
+use fen4::{Board, Color, Piece, TurnColor};
+
+#[test]
+fn resign_free_for_all() {
+    let mut board = Board {
+        points: [1, 0, 0, 0],
+        ..Board::default()
+    };
+    board.resign(TurnColor::Red);
+    assert_eq!(board.extra_options.resigned, [true, false, false, false]);
+    assert_eq!(board.dead, [true, false, false, false]);
+    assert_eq!(board.turn, TurnColor::Blue);
+    assert_eq!(
+        board.board[0][7],
+        Piece::Normal(Color::Dead(Some(TurnColor::Red)), 'K')
+    );
+    assert_eq!(board.material()[0], 0);
+}
+
+#[test]
+fn resign_teams() {
+    let mut board = Board::default();
+    // A capture without any points is how a Teams game is recognized
+    board.board[1][3] = Piece::Empty;
+    let before = board.clone();
+    board.resign(TurnColor::Blue);
+    assert_eq!(board.extra_options.resigned, [false, true, false, false]);
+    assert_eq!(board.dead, [false; 4]);
+    assert!(board.same_pieces(&before));
+}
+
+#[test]
+fn flag() {
+    let mut board = Board {
+        turn: TurnColor::Green,
+        dead: [true, false, false, false],
+        points: [0, 0, 0, 5],
+        ..Board::default()
+    };
+    board.flag(TurnColor::Green);
+    assert_eq!(board.extra_options.flagged, [false, false, false, true]);
+    assert_eq!(board.extra_options.resigned, [false; 4]);
+    assert_eq!(board.dead, [true, false, false, true]);
+    // Red is already dead, so the turn skips to Blue
+    assert_eq!(board.turn, TurnColor::Blue);
+    assert_eq!(
+        board.board[6][13],
+        Piece::Normal(Color::Dead(Some(TurnColor::Green)), 'K')
+    );
+    // Flags round trip through the fen4
+    assert_eq!(board.to_string().parse::<Board>().unwrap(), board);
+}
+
+#[test]
+fn checkmate() {
+    let mut board = Board {
+        turn: TurnColor::Yellow,
+        points: [3, 5, 0, 0],
+        ..Board::default()
+    };
+    board.apply_checkmate(TurnColor::Yellow, TurnColor::Blue);
+    assert_eq!(board.points, [3, 25, 0, 0]);
+    assert_eq!(board.dead, [false, false, true, false]);
+    assert_eq!(board.turn, TurnColor::Green);
+    assert_eq!(
+        board.board[13][6],
+        Piece::Normal(Color::Dead(Some(TurnColor::Yellow)), 'K')
+    );
+    assert_eq!(board.material()[2], 0);
+}
+
+#[test]
+fn checkmate_with_bonus() {
+    let mut board = Board {
+        points: [3, 5, 0, u16::MAX - 4],
+        ..Board::default()
+    };
+    board.apply_checkmate_with(TurnColor::Blue, TurnColor::Red, 12);
+    assert_eq!(board.points, [15, 5, 0, u16::MAX - 4]);
+    assert_eq!(board.dead, [false, true, false, false]);
+    board.apply_checkmate_with(TurnColor::Yellow, TurnColor::Green, 12);
+    assert_eq!(board.points, [15, 5, 0, u16::MAX]);
+    assert!(board.dead[2]);
+    board.apply_checkmate_with(TurnColor::Red, TurnColor::Green, 0);
+    assert_eq!(board.points, [15, 5, 0, u16::MAX]);
+    assert!(board.dead[0]);
+}
+
+#[test]
+fn standings() {
+    let board = Board {
+        points: [12, 31, 12, 40],
+        dead: [false, false, true, false],
+        ..Board::default()
+    };
+    assert_eq!(
+        board.standings(),
+        vec![
+            (TurnColor::Green, 40, true),
+            (TurnColor::Blue, 31, true),
+            (TurnColor::Red, 12, true),
+            (TurnColor::Yellow, 12, false),
+        ]
+    );
+}
+
+#[test]
+fn multiple_royals() {
+    use fen4::Position;
+    let second_king = Piece::Normal(Color::Turn(TurnColor::Blue), 'K');
+    let mut board = Board::default().with_piece(Position { row: 5, col: 5 }, second_king);
+    assert_eq!(
+        board.royal_squares(TurnColor::Blue),
+        vec![Position { row: 5, col: 5 }, Position { row: 7, col: 0 }]
+    );
+    assert_eq!(board.count_royals(TurnColor::Blue), 2);
+    assert_eq!(board.count_royals(TurnColor::Red), 1);
+
+    // The royal tag picks the pieces, which don't have to be kings
+    board.extra_options.royal[1] = vec![Position { row: 6, col: 0 }];
+    assert_eq!(
+        board.royal_squares(TurnColor::Blue),
+        vec![Position { row: 6, col: 0 }]
+    );
+    board.board[6][0] = Piece::Empty;
+    assert_eq!(board.count_royals(TurnColor::Blue), 0);
+}
+
+#[test]
+fn tagged_royals() {
+    use fen4::Position;
+    let fen = "R-0,0,0,0-0,0,0,0-0,0,0,0-0,0,0,0-0-{'royal':('h1 k1','','','')}-14/14/14/14/14/14/14/14/14/14/14/5,yR,8/14/3,rR,3,rK,2,rQ,3";
+    let mut board: Board = fen.parse().unwrap();
+    let (h1, k1) = (Position { row: 0, col: 7 }, Position { row: 0, col: 10 });
+    assert_eq!(board.extra_options.royal[0], vec![h1, k1]);
+    assert_eq!(board.royal_squares(TurnColor::Red), vec![h1, k1]);
+    assert_eq!(board.to_string().replace('\n', ""), fen);
+
+    // Either royal being attacked is check
+    assert!(!board.is_in_check(TurnColor::Red));
+    board.board[2][5] = Piece::Empty;
+    board.board[2][10] = Piece::Normal(Color::Turn(TurnColor::Yellow), 'R');
+    assert!(board.is_in_check(TurnColor::Red));
+    board.board[0][10] = Piece::Empty;
+    assert_eq!(board.royal_squares(TurnColor::Red), vec![h1]);
+    assert!(!board.is_in_check(TurnColor::Red));
+}
+
+#[test]
+fn kill_player() {
+    let mut board = Board::default();
+    board.board[5][5] = Piece::Normal(Color::Dead(None), 'Q');
+    board.kill_player(TurnColor::Blue);
+    assert_eq!(board.dead, [false, true, false, false]);
+    assert_eq!(board.turn, TurnColor::Red);
+    assert_eq!(
+        board.board[3][0],
+        Piece::Normal(Color::Dead(Some(TurnColor::Blue)), 'R')
+    );
+    assert_eq!(board.material(), [39, 0, 39, 39]);
+    // Other pieces are left alone
+    assert_eq!(board.board[5][5], Piece::Normal(Color::Dead(None), 'Q'));
+    assert_eq!(
+        board.board[0][7],
+        Piece::Normal(Color::Turn(TurnColor::Red), 'K')
+    );
+}
+
+#[test]
+fn revive_player() {
+    let mut board = Board::default();
+    board.resign(TurnColor::Green);
+    board.board[5][5] = Piece::Normal(Color::Dead(None), 'Q');
+    board.board[5][6] = Piece::Normal(Color::Dead(Some(TurnColor::Red)), 'N');
+    board.revive_player(TurnColor::Green);
+    assert_eq!(board.dead, [false; 4]);
+    assert_eq!(
+        board.board[6][13],
+        Piece::Normal(Color::Turn(TurnColor::Green), 'K')
+    );
+    assert_eq!(board.material(), [39; 4]);
+    // Unknown and other players' dead pieces stay dead
+    assert_eq!(board.board[5][5], Piece::Normal(Color::Dead(None), 'Q'));
+    assert_eq!(
+        board.board[5][6],
+        Piece::Normal(Color::Dead(Some(TurnColor::Red)), 'N')
+    );
+}
@@ -0,0 +1,161 @@
+use fen4::{Board, Color, Piece, Position, PositionParseError, TurnColor};
+
+#[test]
+fn rotate_default() {
+    let board = Board::default();
+    assert_eq!(board.rotated(4), board);
+    assert_eq!(board.rotated(1).rotated(3), board);
+    let blue_view = board.rotated(1);
+    // Blue's king on a8 ends up on g1
+    assert_eq!(
+        blue_view.board[0][6],
+        Piece::Normal(Color::Turn(TurnColor::Blue), 'K')
+    );
+    assert_eq!(blue_view.turn, board.turn);
+    assert_eq!(blue_view.castling_king, board.castling_king);
+}
+
+#[test]
+fn rotate_extra_positions() {
+    let mut board = Board::default();
+    let d1 = Position { row: 0, col: 3 };
+    let d2 = Position { row: 1, col: 3 };
+    board.extra_options.royal[0] = vec![d1];
+    board.extra_options.enpassant[0] = Some((d1, d2));
+    let rotated = board.rotated(2);
+    let k14 = Position { row: 13, col: 10 };
+    let k13 = Position { row: 12, col: 10 };
+    assert_eq!(rotated.extra_options.royal[0], vec![k14]);
+    assert_eq!(rotated.extra_options.enpassant[0], Some((k14, k13)));
+    assert_eq!(rotated.board[13][10], board.board[0][3]);
+}
+
+#[test]
+fn display_rows_labels() {
+    let board = Board::default();
+    let red: Vec<String> = board
+        .display_rows(TurnColor::Red)
+        .into_iter()
+        .map(|(label, _)| label)
+        .collect();
+    assert_eq!(red[0], "14");
+    assert_eq!(red[13], "1");
+    let blue = board.display_rows(TurnColor::Blue);
+    let labels: Vec<&str> = blue.iter().map(|(label, _)| label.as_str()).collect();
+    assert_eq!(
+        labels,
+        ["n", "m", "l", "k", "j", "i", "h", "g", "f", "e", "d", "c", "b", "a"]
+    );
+    // Blue's back rank is the a-file and is shown at the bottom
+    let (_, bottom) = &blue[13];
+    assert_eq!(bottom[6], Piece::Normal(Color::Turn(TurnColor::Blue), 'K'));
+    let yellow = board.display_rows(TurnColor::Yellow);
+    assert_eq!(yellow[0].0, "1");
+    assert_eq!(yellow[13].0, "14");
+    let green = board.display_rows(TurnColor::Green);
+    assert_eq!(green[0].0, "a");
+    assert_eq!(green[13].0, "n");
+}
+
+#[test]
+fn two_player_perspective() {
+    let mut board = Board::default();
+    assert_eq!(board.two_player_perspective(TurnColor::Red), None);
+    board.extra_options.std2pc = true;
+    let red = board.two_player_perspective(TurnColor::Red).unwrap();
+    assert_eq!(red, board.board);
+    let yellow = board.two_player_perspective(TurnColor::Yellow).unwrap();
+    // Yellow's king on g14 ends up on h1
+    assert_eq!(
+        yellow[0][7],
+        Piece::Normal(Color::Turn(TurnColor::Yellow), 'K')
+    );
+    assert_eq!(
+        yellow[13][6],
+        Piece::Normal(Color::Turn(TurnColor::Red), 'K')
+    );
+    assert_eq!(board.two_player_perspective(TurnColor::Blue), None);
+}
+
+#[test]
+fn distances() {
+    let pairs = [
+        ((0, 7), (0, 7), 0, 0),
+        ((0, 7), (1, 8), 1, 2),
+        ((0, 7), (13, 6), 13, 14),
+        ((7, 0), (6, 13), 13, 14),
+        ((5, 9), (2, 4), 5, 8),
+    ];
+    for &(a, b, chebyshev, manhattan) in &pairs {
+        let (a, b) = (Position::from(a), Position::from(b));
+        assert_eq!(a.chebyshev_distance(&b), chebyshev);
+        assert_eq!(b.chebyshev_distance(&a), chebyshev);
+        assert_eq!(a.manhattan_distance(&b), manhattan);
+        assert_eq!(b.manhattan_distance(&a), manhattan);
+    }
+}
+
+#[test]
+fn playable_positions() {
+    let playable: Vec<Position> = Board::playable_positions().collect();
+    assert_eq!(playable.len(), 160);
+    assert_eq!(playable[0], Position { row: 0, col: 3 });
+    assert_eq!(playable.last(), Some(&Position { row: 13, col: 10 }));
+    assert!(playable.iter().all(Position::is_playable));
+    for &(row, col) in &[(0, 0), (2, 11), (11, 2), (13, 13)] {
+        assert!(!Position { row, col }.is_playable());
+    }
+    assert!(!Position { row: 14, col: 5 }.is_playable());
+    // Every piece in the default position is on a playable square
+    let board = Board::default();
+    let pieces = Board::playable_positions()
+        .filter(|p| board.board[p.row][p.col].is_piece())
+        .count();
+    assert_eq!(pieces, 64);
+}
+
+#[test]
+fn from_coords() {
+    const CENTER: Position = Position::from_coords(6, 7);
+    assert_eq!(CENTER, Position { row: 7, col: 6 });
+    assert_eq!(CENTER, "g8".parse().unwrap());
+    assert_eq!(Position::from_coords(0, 3).to_string(), "a4");
+}
+
+#[test]
+fn column_char_and_row_number() {
+    for row in 0..14 {
+        for col in 0..14 {
+            let pos = Position { row, col };
+            let label = format!("{}{}", pos.column_char(), pos.row_number());
+            assert_eq!(label, pos.to_string());
+            assert_eq!(label.parse::<Position>(), Ok(pos));
+        }
+    }
+    assert_eq!(Position { row: 13, col: 13 }.column_char(), 'n');
+    assert_eq!(Position { row: 13, col: 13 }.row_number(), 14);
+}
+
+#[test]
+#[should_panic]
+fn column_char_off_board() {
+    Position { row: 0, col: 14 }.column_char();
+}
+
+#[test]
+fn position_pairs() {
+    let a1 = Position { row: 0, col: 0 };
+    let n14 = Position { row: 13, col: 13 };
+    assert_eq!(fen4::parse_position_pair("a1:n14"), Ok((a1, n14)));
+    assert_eq!(fen4::parse_position_pair("n14:a1"), Ok((n14, a1)));
+    assert_eq!(
+        fen4::parse_position_pair("a1"),
+        Err(PositionParseError::BadColon)
+    );
+    assert_eq!(
+        fen4::parse_position_pair("a1:a2:a3"),
+        Err(PositionParseError::BadColon)
+    );
+    assert!(fen4::parse_position_pair("a1:z1").is_err());
+    assert!(fen4::parse_position_pair(":a1").is_err());
+}
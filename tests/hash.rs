@@ -0,0 +1,81 @@
+use std::hash::{Hash, Hasher};
+
+use fen4::Board;
+
+// FNV-1a, so the golden values don't depend on the standard library's randomly seeded hasher
+struct Fnv(u64);
+
+impl Hasher for Fnv {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x100_0000_01b3);
+        }
+    }
+}
+
+fn fnv(board: &Board) -> u64 {
+    let mut hasher = Fnv(0xcbf2_9ce4_8422_2325);
+    board.hash(&mut hasher);
+    hasher.finish()
+}
+
+// Hashing writes usizes in native byte order, so these values only hold on 64 bit little endian targets
+#[test]
+#[cfg(all(target_pointer_width = "64", target_endian = "little"))]
+fn golden_hashes() {
+    let extras = "G-0,1,0,0-1,0,1,1-1,1,0,1-5,0,12,3-7-{'royal':('h1','','',''),'lives':(2,2,2,2),'resigned':(false,true,false,false)}-14/14/14/14/14/14/14/14/14/14/14/14/14/3,dK,rK,X,8";
+    let boards = [
+        (Board::default(), 0xa4bd_8a77_9c58_c31e),
+        (Board::chess960(1), 0x850b_a753_8ad8_28be),
+        (extras.parse().unwrap(), 0x3f2e_df2a_a7dc_6fa9),
+    ];
+    for (board, expected) in &boards {
+        assert_eq!(fnv(board), *expected, "Hash changed for {:#}", board);
+    }
+}
+
+#[test]
+fn position_hash() {
+    let board = Board::default();
+    let mut other = board.clone();
+    other.points = [1, 2, 3, 4];
+    other.draw_ply = 12;
+    other.extra_options.lives = Some([2; 4]);
+    assert_eq!(board.position_hash(), other.position_hash());
+
+    let mut turn = board.clone();
+    turn.turn = turn.turn.next();
+    assert_ne!(board.position_hash(), turn.position_hash());
+    let mut castling = board.clone();
+    castling.castling_king[1] = false;
+    assert_ne!(board.position_hash(), castling.position_hash());
+    let mut dead = board.clone();
+    dead.kill_player(fen4::TurnColor::Blue);
+    assert_ne!(board.position_hash(), dead.position_hash());
+}
+
+// Unlike the derived Hash these are the same on every platform
+#[test]
+fn golden_position_hashes() {
+    let fairy: Board = "R-0,0,0,0-0,0,0,0-0,0,0,0-0,0,0,0-0-14/14/14/14/14/14/14/14/14/14/14/14/14/3,yα,drK,dQ,X,7"
+        .parse()
+        .unwrap();
+    let boards = [
+        (Board::default(), 0x282c_db5e_bed3_f9c5),
+        (Board::chess960(1), 0x642e_2abb_ce1f_79eb),
+        (fairy, 0x3e2f_cd2e_c115_86dd),
+    ];
+    for (board, expected) in &boards {
+        assert_eq!(
+            board.position_hash(),
+            *expected,
+            "Position hash changed for {:#}",
+            board
+        );
+    }
+}
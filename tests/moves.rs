@@ -0,0 +1,655 @@
+use fen4::{Board, Color, MoveError, Piece, Position, TurnColor};
+
+fn pos(row: usize, col: usize) -> Position {
+    Position { row, col }
+}
+
+#[test]
+fn make_move() {
+    let mut board = Board {
+        draw_ply: 4,
+        ..Board::default()
+    };
+    // Red knight from j1 to k3 is a quiet move
+    assert_eq!(board.make_move(pos(0, 9), pos(2, 10)), Ok(Piece::Empty));
+    assert_eq!(board.board[0][9], Piece::Empty);
+    assert_eq!(
+        board.board[2][10],
+        Piece::Normal(Color::Turn(TurnColor::Red), 'N')
+    );
+    assert_eq!(board.draw_ply, 5);
+    assert_eq!(board.turn, TurnColor::Blue);
+
+    // Blue pawn from b4 to c4 resets the counter
+    assert_eq!(board.make_move(pos(3, 1), pos(3, 2)), Ok(Piece::Empty));
+    assert_eq!(board.draw_ply, 0);
+    assert_eq!(board.turn, TurnColor::Yellow);
+}
+
+#[test]
+fn make_move_capture() {
+    let mut board = Board {
+        draw_ply: 7,
+        ..Board::default()
+    };
+    // Red rook takes the blue pawn on b4 (not a legal move, but make_move does not check)
+    assert_eq!(
+        board.make_move(pos(0, 3), pos(3, 1)),
+        Ok(Piece::Normal(Color::Turn(TurnColor::Blue), 'P'))
+    );
+    assert_eq!(board.draw_ply, 0);
+}
+
+#[test]
+fn make_move_errors() {
+    let mut board = Board::default();
+    assert_eq!(
+        board.make_move(pos(5, 5), pos(6, 5)),
+        Err(MoveError::EmptySource)
+    );
+    assert_eq!(
+        board.make_move(pos(1, 5), pos(14, 5)),
+        Err(MoveError::OffBoard(pos(14, 5)))
+    );
+    assert_eq!(board, Board::default());
+}
+
+#[test]
+fn rook_moves() {
+    let red_rook = Piece::Normal(Color::Turn(TurnColor::Red), 'R');
+    let mut board = Board::default().with_piece(pos(5, 5), red_rook);
+    board.board[5][8] = Piece::Normal(Color::Dead(None), 'P');
+    board.board[5][3] = Piece::Wall;
+    board.board[3][5] = Piece::Normal(Color::Turn(TurnColor::Red), 'N');
+    let mut moves = board.rook_moves(pos(5, 5));
+    moves.sort();
+    assert_eq!(
+        moves,
+        vec![
+            pos(4, 5),
+            pos(5, 4),
+            pos(5, 6),
+            pos(5, 7),
+            pos(5, 8),
+            pos(6, 5),
+            pos(7, 5),
+            pos(8, 5),
+            pos(9, 5),
+            pos(10, 5),
+            pos(11, 5),
+            pos(12, 5),
+        ]
+    );
+    assert!(board.rook_moves(pos(7, 7)).is_empty());
+}
+
+#[test]
+fn rook_stays_out_of_corners() {
+    let red_rook = Piece::Normal(Color::Turn(TurnColor::Red), 'R');
+    let board = Board::default().with_piece(pos(2, 3), red_rook);
+    let moves = board.rook_moves(pos(2, 3));
+    assert_eq!(moves.len(), 17);
+    assert!(moves.contains(&pos(2, 10)));
+    assert!(moves.contains(&pos(12, 3)));
+    assert!(!moves.contains(&pos(2, 2)));
+    assert!(!moves.contains(&pos(2, 11)));
+}
+
+#[test]
+fn knight_moves() {
+    let board = Board::default();
+    let mut moves = board.knight_moves(pos(0, 9));
+    moves.sort();
+    assert_eq!(moves, vec![pos(2, 8), pos(2, 10)]);
+    // b3 is in the corner and b7 has Blue's own pawn
+    let mut moves = board.knight_moves(pos(4, 0));
+    moves.sort();
+    assert_eq!(moves, vec![pos(3, 2), pos(5, 2)]);
+}
+
+#[test]
+fn knight_stays_out_of_corners() {
+    let red_knight = Piece::Normal(Color::Turn(TurnColor::Red), 'N');
+    let board = Board::default()
+        .with_piece(pos(3, 3), red_knight)
+        .with_piece(pos(5, 4), Piece::Wall);
+    let mut moves = board.knight_moves(pos(3, 3));
+    moves.sort();
+    // b3 and c2 are corner squares, e2 has a red pawn, and e6 is a wall
+    assert_eq!(moves, vec![pos(2, 5), pos(4, 1), pos(4, 5), pos(5, 2)]);
+}
+
+#[test]
+fn king_moves_near_corner() {
+    let red_king = Piece::Normal(Color::Turn(TurnColor::Red), 'K');
+    let board = Board::default()
+        .with_piece(pos(3, 3), red_king)
+        .with_piece(pos(4, 4), Piece::Wall);
+    let mut moves: Vec<Position> = board
+        .legal_king_moves(pos(3, 3), TurnColor::Red)
+        .into_iter()
+        .map(|m| {
+            assert_eq!(m.from, pos(3, 3));
+            m.to
+        })
+        .collect();
+    moves.sort();
+    // c3 is a corner square, d3 and e3 are free, c4 and c5 are empty, e5 is a wall
+    assert_eq!(
+        moves,
+        vec![
+            pos(2, 3),
+            pos(2, 4),
+            pos(3, 2),
+            pos(3, 4),
+            pos(4, 2),
+            pos(4, 3)
+        ]
+    );
+}
+
+#[test]
+fn make_move_dead_king() {
+    let mut board = Board::default();
+    board.resign(TurnColor::Red);
+    assert_eq!(board.turn, TurnColor::Blue);
+    board.turn = TurnColor::Red;
+    board.board[1][7] = Piece::Empty;
+    assert_eq!(board.make_move(pos(0, 7), pos(1, 7)), Ok(Piece::Empty));
+    assert_eq!(
+        board.board[1][7],
+        Piece::Normal(Color::Dead(Some(TurnColor::Red)), 'K')
+    );
+    assert_eq!(board.turn, TurnColor::Blue);
+    assert_eq!(board.draw_ply, 1);
+
+    // Turns pass to dead players too
+    board.turn = TurnColor::Green;
+    board.make_move(pos(1, 3), pos(2, 3)).unwrap();
+    assert_eq!(board.turn, TurnColor::Red);
+}
+
+#[test]
+fn pawn_is_blocked() {
+    let yellow_pawn = Piece::Normal(Color::Turn(TurnColor::Yellow), 'P');
+    let board = Board::default().with_piece(pos(2, 4), yellow_pawn);
+    // Red's e2 pawn runs into the yellow pawn on e3, f2 is free to advance
+    assert!(board.pawn_is_blocked(pos(1, 4), TurnColor::Red));
+    assert!(!board.pawn_is_blocked(pos(1, 5), TurnColor::Red));
+    // Yellow's pawn on e3 faces Red's pawn on e2
+    assert!(board.pawn_is_blocked(pos(2, 4), TurnColor::Yellow));
+    // Green's pawn on m4 moves towards the empty l4
+    assert!(!board.pawn_is_blocked(pos(3, 12), TurnColor::Green));
+}
+
+#[test]
+fn apply_move_reporting_check() {
+    use fen4::{Move, MoveReport};
+    let red_rook = Piece::Normal(Color::Turn(TurnColor::Red), 'R');
+    let mut board = Board::default()
+        .with_piece(pos(4, 4), red_rook)
+        .with_piece(pos(12, 6), Piece::Empty);
+    // The rook slides to g5 and looks up the open g file at Yellow's king on g14
+    let report = board.apply_move_reporting(Move {
+        from: pos(4, 4),
+        to: pos(4, 6),
+    });
+    assert_eq!(
+        report,
+        Ok(MoveReport {
+            captured: Piece::Empty,
+            promoted: false,
+            checks: [false, false, true, false],
+        })
+    );
+
+    // A dead Yellow can't be in check
+    let mut board = Board::default()
+        .with_piece(pos(4, 4), Piece::Normal(Color::Turn(TurnColor::Red), 'R'))
+        .with_piece(pos(12, 6), Piece::Empty);
+    board.kill_player(TurnColor::Yellow);
+    let report = board.apply_move_reporting(Move {
+        from: pos(4, 4),
+        to: pos(4, 6),
+    });
+    assert_eq!(report.unwrap().checks, [false; 4]);
+}
+
+#[test]
+fn apply_move_reporting_promotion() {
+    use fen4::Move;
+    let blue_pawn = Piece::Normal(Color::Turn(TurnColor::Blue), 'P');
+    let mut board = Board::default().with_piece(pos(5, 6), blue_pawn);
+    let report = board
+        .apply_move_reporting(Move {
+            from: pos(5, 6),
+            to: pos(5, 7),
+        })
+        .unwrap();
+    assert!(report.promoted);
+    assert_eq!(
+        board.board[5][7],
+        Piece::Normal(Color::Turn(TurnColor::Blue), 'Q')
+    );
+    // Red's pawn on h7 isn't on its promotion rank yet
+    let red_pawn = Piece::Normal(Color::Turn(TurnColor::Red), 'P');
+    let mut board = Board::default().with_piece(pos(5, 7), red_pawn);
+    let report = board
+        .apply_move_reporting(Move {
+            from: pos(5, 7),
+            to: pos(6, 7),
+        })
+        .unwrap();
+    assert!(!report.promoted);
+}
+
+#[test]
+fn legal_moves() {
+    let board = Board::default();
+    for color in TurnColor::iter() {
+        assert_eq!(board.legal_moves(color).len(), 20);
+    }
+    // Red's i2 pawn is pinned to the king on h1 by a green bishop on k4
+    let green_bishop = Piece::Normal(Color::Turn(TurnColor::Green), 'B');
+    let board = Board::default().with_piece(pos(3, 10), green_bishop);
+    let moves = board.legal_moves(TurnColor::Red);
+    assert!(moves.iter().all(|m| m.from != pos(1, 8)));
+    // The k2 pawn can't jump onto the bishop either, but can capture it from j3
+    assert_eq!(moves.len(), 17);
+    let board = board.with_piece(pos(2, 9), Piece::Normal(Color::Turn(TurnColor::Red), 'P'));
+    assert!(board.legal_moves(TurnColor::Red).contains(&fen4::Move {
+        from: pos(2, 9),
+        to: pos(3, 10)
+    }));
+}
+
+#[test]
+fn legal_moves_pawns_base_rank() {
+    let double_steps = |board: &Board, color| {
+        board
+            .legal_moves(color)
+            .iter()
+            .filter(|m| {
+                m.from.chebyshev_distance(&m.to) == 2 && m.from.manhattan_distance(&m.to) == 2
+            })
+            .filter(|m| matches!(board.board[m.from.row][m.from.col], Piece::Normal(_, 'P')))
+            .count()
+    };
+    let mut board = Board::default();
+    for color in TurnColor::iter() {
+        assert_eq!(double_steps(&board, color), 8);
+    }
+    // Pawns one rank further up jump instead, counting from each player's own side
+    board
+        .set_extra("pawnsBaseRank", fen4::ExtraValue::Number(3))
+        .unwrap();
+    for color in TurnColor::iter() {
+        assert_eq!(double_steps(&board, color), 0);
+    }
+    let red = Piece::Normal(Color::Turn(TurnColor::Red), 'P');
+    let green = Piece::Normal(Color::Turn(TurnColor::Green), 'P');
+    let board = board
+        .with_piece(pos(2, 5), red)
+        .with_piece(pos(5, 11), green);
+    assert_eq!(double_steps(&board, TurnColor::Red), 1);
+    assert_eq!(double_steps(&board, TurnColor::Green), 1);
+    assert!(board.legal_moves(TurnColor::Green).contains(&fen4::Move {
+        from: pos(5, 11),
+        to: pos(5, 9)
+    }));
+    // 0 turns the double step off
+    let mut never = Board::default();
+    never
+        .set_extra("pawnsBaseRank", fen4::ExtraValue::Number(0))
+        .unwrap();
+    assert_eq!(never.legal_moves(TurnColor::Red).len(), 12);
+}
+
+#[test]
+fn legal_moves_tagged_royal_moves() {
+    // The king is royal through the tag, and may not step onto the i-file the yellow rook covers
+    let fen = "R-0,0,0,0-0,0,0,0-0,0,0,0-0,0,0,0-0-{'royal':('h1','','','')}-8,yR,5/14/14/14/14/14/14/14/14/14/14/14/14/7,rK,6";
+    let board: Board = fen.parse().unwrap();
+    let moves = board.legal_moves(TurnColor::Red);
+    assert!(!moves.iter().any(|m| m.to.col == 8), "{:?}", moves);
+    let mut targets: Vec<Position> = moves.iter().map(|m| m.to).collect();
+    targets.sort();
+    assert_eq!(targets, vec![pos(0, 6), pos(1, 6), pos(1, 7)]);
+}
+
+#[test]
+fn promote() {
+    let mut board = Board::default();
+    board.resign(TurnColor::Green);
+    // Living and dead pawns both keep their color
+    assert_eq!(board.promote(pos(12, 5), 'N'), Ok(()));
+    assert_eq!(
+        board.board[12][5],
+        Piece::Normal(Color::Turn(TurnColor::Yellow), 'N')
+    );
+    assert_eq!(board.promote(pos(5, 12), 'α'), Ok(()));
+    assert_eq!(
+        board.board[5][12],
+        Piece::Normal(Color::Dead(Some(TurnColor::Green)), 'α')
+    );
+
+    let before = board.clone();
+    assert_eq!(board.promote(pos(6, 6), 'Q'), Err(MoveError::EmptySource));
+    assert_eq!(
+        board.promote(pos(0, 7), 'Q'),
+        Err(MoveError::NotAPawn(pos(0, 7)))
+    );
+    board.board[6][6] = Piece::Wall;
+    assert_eq!(
+        board.promote(pos(6, 6), 'Q'),
+        Err(MoveError::NotAPawn(pos(6, 6)))
+    );
+    board.board[6][6] = Piece::Empty;
+    assert_eq!(
+        board.promote(pos(14, 0), 'Q'),
+        Err(MoveError::OffBoard(pos(14, 0)))
+    );
+    assert_eq!(board, before);
+}
+
+#[test]
+fn describe_move() {
+    let red = |shape| Piece::Normal(Color::Turn(TurnColor::Red), shape);
+    let blue = |shape| Piece::Normal(Color::Turn(TurnColor::Blue), shape);
+    let pos = |s: &str| s.parse::<Position>().unwrap();
+    let board = Board::empty()
+        .with_piece(pos("d4"), red('R'))
+        .with_piece(pos("k4"), red('R'))
+        .with_piece(pos("g8"), red('N'))
+        .with_piece(pos("g10"), red('N'))
+        .with_piece(pos("e6"), red('N'))
+        .with_piece(pos("h7"), blue('Q'))
+        .with_piece(pos("g6"), red('P'))
+        .with_piece(pos("e5"), blue('P'))
+        .with_piece(pos("f6"), red('Z'));
+    let describe = |from, to| board.describe_move(pos(from), pos(to));
+
+    assert_eq!(describe("d4", "f4"), Ok("Rdf4".to_string()));
+    assert_eq!(describe("d4", "d7"), Ok("Rd7".to_string()));
+    assert_eq!(describe("g8", "e9"), Ok("N8e9".to_string()));
+    assert_eq!(describe("e6", "f8"), Ok("Nef8".to_string()));
+    assert_eq!(describe("g6", "h7"), Ok("gxh7".to_string()));
+    assert_eq!(describe("g6", "g7"), Ok("g7".to_string()));
+    assert_eq!(describe("e5", "f6"), Ok("5xf6".to_string()));
+    assert_eq!(describe("f6", "f7"), Ok("Zf7".to_string()));
+    assert_eq!(describe("h7", "g6"), Ok("Qxg6".to_string()));
+    assert_eq!(describe("a5", "a6"), Err(MoveError::EmptySource));
+}
+
+#[test]
+fn castle() {
+    use fen4::CastleSide;
+    let clear = |board: &mut Board, squares: &[(usize, usize)]| {
+        for &(row, col) in squares {
+            board.board[row][col] = Piece::Empty;
+        }
+    };
+    let mut board = Board::default();
+    assert_eq!(
+        board.castle(TurnColor::Blue, CastleSide::Queen),
+        Err(MoveError::CastlingBlocked(TurnColor::Blue))
+    );
+
+    // Blue's queen side is towards row 3
+    clear(&mut board, &[(4, 0), (5, 0), (6, 0)]);
+    board.castle(TurnColor::Blue, CastleSide::Queen).unwrap();
+    let blue = |shape| Piece::Normal(Color::Turn(TurnColor::Blue), shape);
+    assert_eq!(board.board[5][0], blue('K'));
+    assert_eq!(board.board[6][0], blue('R'));
+    assert_eq!(board.board[3][0], Piece::Empty);
+    assert_eq!(board.board[7][0], Piece::Empty);
+    assert_eq!(board.castling_king, [true, false, true, true]);
+    assert_eq!(board.castling_queen, [true, false, true, true]);
+    assert_eq!(
+        board.castle(TurnColor::Blue, CastleSide::King),
+        Err(MoveError::NoCastlingRights(
+            TurnColor::Blue,
+            CastleSide::King
+        ))
+    );
+
+    // Yellow's king side is towards column 3
+    clear(&mut board, &[(13, 4), (13, 5)]);
+    board.castle(TurnColor::Yellow, CastleSide::King).unwrap();
+    let yellow = |shape| Piece::Normal(Color::Turn(TurnColor::Yellow), shape);
+    assert_eq!(board.board[13][4], yellow('K'));
+    assert_eq!(board.board[13][5], yellow('R'));
+
+    // Green's king side is towards row 3
+    clear(&mut board, &[(3, 13)]);
+    assert_eq!(
+        board.castle(TurnColor::Green, CastleSide::King),
+        Err(MoveError::EmptySource)
+    );
+}
+
+#[test]
+fn update_castling_after_move() {
+    let cases = [
+        // (king, king side rook, queen side rook)
+        (TurnColor::Red, pos(0, 7), pos(0, 10), pos(0, 3)),
+        (TurnColor::Blue, pos(7, 0), pos(10, 0), pos(3, 0)),
+        (TurnColor::Yellow, pos(13, 6), pos(13, 3), pos(13, 10)),
+        (TurnColor::Green, pos(6, 13), pos(3, 13), pos(10, 13)),
+    ];
+    for &(color, king, king_rook, queen_rook) in &cases {
+        let index = usize::from(color);
+        let mut only = [true; 4];
+        only[index] = false;
+
+        let mut board = Board::default();
+        board.update_castling_after_move(king);
+        assert_eq!(board.castling_king, only, "{} king", color);
+        assert_eq!(board.castling_queen, only, "{} king", color);
+
+        let mut board = Board::default();
+        board.update_castling_after_move(king_rook);
+        assert_eq!(board.castling_king, only, "{} king side rook", color);
+        assert_eq!(board.castling_queen, [true; 4], "{} king side rook", color);
+
+        let mut board = Board::default();
+        board.update_castling_after_move(queen_rook);
+        assert_eq!(board.castling_king, [true; 4], "{} queen side rook", color);
+        assert_eq!(board.castling_queen, only, "{} queen side rook", color);
+    }
+
+    let mut board = Board::default();
+    board.update_castling_after_move(pos(1, 7));
+    board.update_castling_after_move(pos(0, 6));
+    assert_eq!(board.castling_king, [true; 4]);
+    assert_eq!(board.castling_queen, [true; 4]);
+}
+
+#[test]
+fn en_passant() {
+    let mut board = Board::default();
+    for color in TurnColor::iter() {
+        assert_eq!(board.en_passant_for(color), None);
+    }
+    board.set_en_passant(TurnColor::Green, pos(5, 11), pos(5, 10));
+    assert_eq!(
+        board.extra_options.enpassant[3],
+        Some((pos(5, 11), pos(5, 10)))
+    );
+    assert_eq!(
+        board.en_passant_for(TurnColor::Green),
+        Some((pos(5, 11), pos(5, 10)))
+    );
+    assert_eq!(board.en_passant_for(TurnColor::Red), None);
+
+    board.set_en_passant(TurnColor::Green, pos(8, 11), pos(8, 10));
+    assert_eq!(
+        board.en_passant_for(TurnColor::Green),
+        Some((pos(8, 11), pos(8, 10)))
+    );
+    board.clear_en_passant(TurnColor::Green);
+    assert_eq!(board, Board::default());
+}
+
+#[test]
+fn is_in_check_by_pawns() {
+    let king = |color| Piece::Normal(Color::Turn(color), 'K');
+    let pawn = |color| Piece::Normal(Color::Turn(color), 'P');
+    let center = pos(6, 6);
+    // Where each color's pawn has to stand to attack the center
+    let attackers = [
+        (TurnColor::Red, [pos(5, 5), pos(5, 7)]),
+        (TurnColor::Blue, [pos(5, 5), pos(7, 5)]),
+        (TurnColor::Yellow, [pos(7, 5), pos(7, 7)]),
+        (TurnColor::Green, [pos(5, 7), pos(7, 7)]),
+    ];
+    for &(attacker, squares) in &attackers {
+        let defender = attacker.next();
+        for &square in &squares {
+            let board = Board::empty()
+                .with_piece(center, king(defender))
+                .with_piece(square, pawn(attacker));
+            assert!(
+                board.is_in_check(defender),
+                "{} pawn on {}",
+                attacker,
+                square
+            );
+        }
+        let all = [
+            pos(5, 5),
+            pos(5, 7),
+            pos(7, 5),
+            pos(7, 7),
+            pos(5, 6),
+            pos(7, 6),
+            pos(6, 5),
+            pos(6, 7),
+        ];
+        for square in all.iter().filter(|s| !squares.contains(s)) {
+            let board = Board::empty()
+                .with_piece(center, king(defender))
+                .with_piece(*square, pawn(attacker));
+            assert!(
+                !board.is_in_check(defender),
+                "{} pawn on {}",
+                attacker,
+                square
+            );
+        }
+    }
+}
+
+#[test]
+fn is_in_check_royal() {
+    let queen = Piece::Normal(Color::Turn(TurnColor::Yellow), 'Q');
+    let mut board = Board::default().with_piece(pos(4, 4), queen);
+    board.board[1][4] = Piece::Empty;
+    board.board[1][7] = Piece::Empty;
+    // The king on h1 is safe, but the knight on e1 is attacked
+    assert!(!board.is_in_check(TurnColor::Red));
+    board.extra_options.royal[0] = vec![pos(0, 4)];
+    assert!(board.is_in_check(TurnColor::Red));
+
+    // Dead pieces don't give check
+    board.board[4][4] = Piece::Normal(Color::Dead(Some(TurnColor::Yellow)), 'Q');
+    assert!(!board.is_in_check(TurnColor::Red));
+}
+
+#[test]
+fn is_promotion_square() {
+    let board = Board::default();
+    let promotes = |color, row, col| board.is_promotion_square(color, pos(row, col));
+    for i in 0..14 {
+        assert!(promotes(TurnColor::Red, 7, i));
+        assert!(promotes(TurnColor::Blue, i, 7));
+        assert!(promotes(TurnColor::Yellow, 6, i));
+        assert!(promotes(TurnColor::Green, i, 6));
+        assert!(!promotes(TurnColor::Red, 6, i));
+        assert!(!promotes(TurnColor::Blue, i, 6));
+        assert!(!promotes(TurnColor::Yellow, 7, i));
+        assert!(!promotes(TurnColor::Green, i, 7));
+        assert!(!promotes(TurnColor::Red, 13, i));
+        assert!(!promotes(TurnColor::Yellow, 0, i));
+    }
+    assert!(!promotes(TurnColor::Red, 7, 14));
+
+    // A pawn walking forward from its starting square reaches the promotion line after 6 steps
+    let starts = [pos(1, 5), pos(5, 1), pos(12, 5), pos(5, 12)];
+    for (color, &start) in TurnColor::iter().zip(&starts) {
+        let (dr, dc) = color.pawn_forward();
+        let steps = (1..14)
+            .find(|&n| {
+                let row = (start.row as isize + dr * n) as usize;
+                let col = (start.col as isize + dc * n) as usize;
+                promotes(color, row, col)
+            })
+            .unwrap();
+        assert_eq!(steps, 6, "{}", color);
+    }
+}
+
+#[test]
+fn for_each_legal_move_agrees_with_legal_moves() {
+    use std::ops::ControlFlow;
+    let green_bishop = Piece::Normal(Color::Turn(TurnColor::Green), 'B');
+    let boards = [
+        Board::default(),
+        Board::default().with_piece(pos(3, 10), green_bishop),
+        Board::chess960(7),
+    ];
+    for board in &boards {
+        for color in TurnColor::iter() {
+            let mut visited = Vec::new();
+            let flow = board.for_each_legal_move(color, |mv| {
+                visited.push(mv);
+                ControlFlow::<()>::Continue(())
+            });
+            assert_eq!(flow, ControlFlow::Continue(()));
+            assert_eq!(visited, board.legal_moves(color));
+        }
+    }
+
+    // Breaking stops the generator right away
+    let board = Board::default();
+    let mut seen = 0;
+    let flow = board.for_each_legal_move(TurnColor::Blue, |mv| {
+        seen += 1;
+        if seen == 3 {
+            ControlFlow::Break(mv)
+        } else {
+            ControlFlow::Continue(())
+        }
+    });
+    assert_eq!(seen, 3);
+    assert_eq!(
+        flow,
+        ControlFlow::Break(board.legal_moves(TurnColor::Blue)[2])
+    );
+}
+
+#[test]
+fn is_stalemate() {
+    // Red's king on d1 has the corner on one side and a yellow queen covering every other square
+    let king = Piece::Normal(Color::Turn(TurnColor::Red), 'K');
+    let queen = Piece::Normal(Color::Turn(TurnColor::Yellow), 'Q');
+    let board = Board::empty()
+        .with_piece(pos(0, 3), king.clone())
+        .with_piece(pos(2, 4), queen.clone());
+    assert_eq!(board.turn, TurnColor::Red);
+    assert!(!board.is_in_check(TurnColor::Red));
+    assert!(board.legal_moves(TurnColor::Red).is_empty());
+    assert!(board.is_stalemate());
+
+    // In check with no moves is mate, not stalemate
+    let mated = board.clone().with_piece(pos(2, 3), queen);
+    assert!(mated.is_in_check(TurnColor::Red));
+    assert!(!mated.is_stalemate());
+    // A pawn that can still move breaks the stalemate
+    let pawn = Piece::Normal(Color::Turn(TurnColor::Red), 'P');
+    assert!(!board.clone().with_piece(pos(1, 8), pawn).is_stalemate());
+    // A resigned player isn't stalemated
+    let mut resigned = board;
+    resigned.dead[0] = true;
+    assert!(!resigned.is_stalemate());
+}
@@ -0,0 +1,53 @@
+use fen4::{replay_pgn4, Board, Color, MoveError, Pgn4Error, Piece, Position, TurnColor};
+
+#[test]
+fn replay_moves() {
+    let moves = "1. h2-h3 .. b7-c7 .. j13-j12 .. m7-l7 2. Qg1-j4 .. c7-d7 .. Bi14xl11+ .. l7-k7";
+    let boards = replay_pgn4(&Board::default(), moves).unwrap();
+    assert_eq!(boards.len(), 8);
+    assert_eq!(
+        boards[0].board[2][7],
+        Piece::Normal(Color::Turn(TurnColor::Red), 'P')
+    );
+    assert_eq!(boards[0].board[1][7], Piece::Empty);
+    let last = boards.last().unwrap();
+    assert_eq!(last.turn, TurnColor::Red);
+    assert_eq!(
+        last.board[10][11],
+        Piece::Normal(Color::Turn(TurnColor::Yellow), 'B')
+    );
+    assert_eq!(
+        last.board[3][9],
+        Piece::Normal(Color::Turn(TurnColor::Red), 'Q')
+    );
+    assert!(replay_pgn4(&Board::default(), "").unwrap().is_empty());
+}
+
+#[test]
+fn promotion() {
+    let mut start = Board::empty();
+    start.board[6][4] = Piece::Normal(Color::Turn(TurnColor::Red), 'P');
+    let boards = replay_pgn4(&start, "1. e7-e8=D").unwrap();
+    assert_eq!(
+        boards[0].board[7][4],
+        Piece::Normal(Color::Turn(TurnColor::Red), 'D')
+    );
+}
+
+#[test]
+fn errors() {
+    let board = Board::default();
+    assert_eq!(
+        replay_pgn4(&board, "1. h2-h3 .. b7c7"),
+        Err(Pgn4Error::BadMove(2, "b7c7".to_string()))
+    );
+    assert_eq!(replay_pgn4(&board, "1. O-O"), Err(Pgn4Error::Castling(1)));
+    assert_eq!(
+        replay_pgn4(&board, "1. Nh2-h3"),
+        Err(Pgn4Error::WrongPiece(1, 'N', Position { row: 1, col: 7 }))
+    );
+    assert_eq!(
+        replay_pgn4(&board, "1. h5-h6"),
+        Err(Pgn4Error::Illegal(1, MoveError::EmptySource))
+    );
+}
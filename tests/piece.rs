@@ -0,0 +1,159 @@
+use fen4::{Color, Piece, PieceKind, TurnColor};
+
+#[test]
+fn kinds() {
+    let red = Color::Turn(TurnColor::Red);
+    for (shape, kind) in &[
+        ('P', PieceKind::Pawn),
+        ('N', PieceKind::Knight),
+        ('B', PieceKind::Bishop),
+        ('R', PieceKind::Rook),
+        ('Q', PieceKind::Queen),
+        ('K', PieceKind::King),
+        ('α', PieceKind::Fairy('α')),
+    ] {
+        assert_eq!(Piece::Normal(red, *shape).kind(), Some(*kind));
+        assert_eq!(char::from(*kind), *shape);
+    }
+    assert_eq!(Piece::Empty.kind(), None);
+    assert_eq!(Piece::Wall.kind(), None);
+    assert_eq!(
+        Piece::Normal(Color::Dead(None), 'K').kind(),
+        Some(PieceKind::King)
+    );
+}
+
+#[test]
+fn colors() {
+    let blue = Piece::Normal(Color::Turn(TurnColor::Blue), 'N');
+    assert_eq!(blue.color(), Some(Color::Turn(TurnColor::Blue)));
+    assert_eq!(blue.turn_color(), Some(TurnColor::Blue));
+    let dead = Piece::Normal(Color::Dead(Some(TurnColor::Red)), 'K');
+    assert_eq!(dead.color(), Some(Color::Dead(Some(TurnColor::Red))));
+    assert_eq!(dead.turn_color(), Some(TurnColor::Red));
+    let unknown = Piece::Normal(Color::Dead(None), 'K');
+    assert_eq!(unknown.color(), Some(Color::Dead(None)));
+    assert_eq!(unknown.turn_color(), None);
+    for piece in &[Piece::Empty, Piece::Wall] {
+        assert_eq!(piece.color(), None);
+        assert_eq!(piece.turn_color(), None);
+    }
+}
+
+#[test]
+fn with_color() {
+    let knight = Piece::Normal(Color::Turn(TurnColor::Blue), 'N');
+    let green = knight.with_color(Color::Turn(TurnColor::Green));
+    assert_eq!(green, Piece::Normal(Color::Turn(TurnColor::Green), 'N'));
+    assert_eq!(green.kind(), knight.kind());
+    assert_eq!(
+        Piece::Normal(Color::Dead(None), 'α').with_color(Color::Turn(TurnColor::Red)),
+        Piece::Normal(Color::Turn(TurnColor::Red), 'α')
+    );
+    assert_eq!(Piece::Empty.with_color(Color::Dead(None)), Piece::Empty);
+    assert_eq!(Piece::Wall.with_color(Color::Dead(None)), Piece::Wall);
+}
+
+#[test]
+fn shape_str() {
+    let queen = Piece::Normal(Color::Turn(TurnColor::Red), 'Q');
+    assert_eq!(queen.shape_str(), Some("Q".to_string()));
+    assert_eq!(Piece::Empty.shape_str(), None);
+    assert_eq!(Piece::Wall.shape_str(), None);
+}
+
+#[test]
+#[cfg(not(feature = "multi-char-fairy"))]
+fn multi_char_shapes_rejected() {
+    assert!("rAB".parse::<Piece>().is_err());
+    assert!("drAB".parse::<Piece>().is_err());
+}
+
+#[test]
+#[cfg(feature = "multi-char-fairy")]
+fn multi_char_shapes() {
+    use fen4::{Board, Position};
+    let camel = Piece::Fairy(Color::Turn(TurnColor::Blue), "Camel".to_string());
+    assert_eq!("bCamel".parse::<Piece>(), Ok(camel.clone()));
+    assert_eq!(camel.to_string(), "bCamel");
+    assert_eq!(camel.shape_str(), Some("Camel".to_string()));
+    assert_eq!(camel.color(), Some(Color::Turn(TurnColor::Blue)));
+    assert_eq!(camel.kind(), None);
+    assert!(camel.is_piece());
+    assert_eq!(
+        "drαβ".parse::<Piece>(),
+        Ok(Piece::Fairy(
+            Color::Dead(Some(TurnColor::Red)),
+            "αβ".to_string()
+        ))
+    );
+    // Single character shapes stay Normal
+    assert_eq!(
+        "bC".parse::<Piece>(),
+        Ok(Piece::Normal(Color::Turn(TurnColor::Blue), 'C'))
+    );
+
+    let at = Position { row: 5, col: 0 };
+    let mut board = Board::default().with_piece(at, camel.clone());
+    let fen = board.to_string();
+    assert!(fen.contains("bCamel"));
+    assert_eq!(fen.parse::<Board>(), Ok(board.clone()));
+    assert_eq!(Board::from_bytes(&board.to_bytes()), Ok(board.clone()));
+    assert!(Board::from_ascii(&board.to_ascii())
+        .unwrap()
+        .same_pieces(&board));
+    assert_ne!(
+        board.position_hash(),
+        Board::default()
+            .with_piece(
+                at,
+                Piece::Fairy(Color::Turn(TurnColor::Blue), "Camle".to_string())
+            )
+            .position_hash()
+    );
+
+    board.kill_player(TurnColor::Blue);
+    let dead = camel.with_color(Color::Dead(Some(TurnColor::Blue)));
+    assert_eq!(board.board[5][0], dead);
+    assert!(board.dead_pieces().contains(&dead));
+    assert_eq!(Board::from_bytes(&board.to_bytes()), Ok(board.clone()));
+    assert!(Board::from_ascii(&board.to_ascii())
+        .unwrap()
+        .same_pieces(&board));
+}
+
+#[test]
+fn greek_shapes() {
+    let colors = [
+        ("r", Color::Turn(TurnColor::Red)),
+        ("b", Color::Turn(TurnColor::Blue)),
+        ("y", Color::Turn(TurnColor::Yellow)),
+        ("g", Color::Turn(TurnColor::Green)),
+        ("d", Color::Dead(None)),
+        ("dr", Color::Dead(Some(TurnColor::Red))),
+        ("dg", Color::Dead(Some(TurnColor::Green))),
+    ];
+    for &shape in &['α', 'β', 'γ', 'δ'] {
+        for (prefix, color) in &colors {
+            let text = format!("{}{}", prefix, shape);
+            let piece = Piece::Normal(*color, shape);
+            assert_eq!(text.parse::<Piece>(), Ok(piece.clone()), "{}", text);
+            assert_eq!(piece.to_string(), text);
+            assert_eq!(piece.kind(), Some(PieceKind::Fairy(shape)));
+        }
+    }
+}
+
+#[test]
+fn bad_sizes_count_chars() {
+    use fen4::PieceParseError::BadSize;
+    assert_eq!("".parse::<Piece>(), Err(BadSize(0)));
+    assert_eq!("y".parse::<Piece>(), Err(BadSize(1)));
+    assert_eq!("dy".parse::<Piece>(), Err(BadSize(2)));
+    #[cfg(not(feature = "multi-char-fairy"))]
+    {
+        assert_eq!("yαβ".parse::<Piece>(), Err(BadSize(3)));
+        assert_eq!("dyαβ".parse::<Piece>(), Err(BadSize(4)));
+        assert_eq!("rKQB".parse::<Piece>(), Err(BadSize(4)));
+    }
+}
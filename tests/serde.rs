@@ -0,0 +1,26 @@
+#![cfg(feature = "serde")]
+use fen4::{Board, Color, Piece, Position, TurnColor};
+
+#[test]
+fn board_is_fen_string() {
+    let board = Board::default();
+    let json = serde_json::to_string(&board).unwrap();
+    assert_eq!(json, serde_json::to_string(&board.to_string()).unwrap());
+    let board2: Board = serde_json::from_str(&json).unwrap();
+    assert_eq!(board, board2);
+}
+
+#[test]
+fn invalid_fen_is_an_error() {
+    assert!(serde_json::from_str::<Board>("\"not a fen\"").is_err());
+}
+
+#[test]
+fn piece_round_trip() {
+    let piece = Piece::Normal(Color::Dead(Some(TurnColor::Green)), 'Q');
+    let json = serde_json::to_string(&piece).unwrap();
+    assert_eq!(piece, serde_json::from_str::<Piece>(&json).unwrap());
+    let position = Position { row: 3, col: 7 };
+    let json = serde_json::to_string(&position).unwrap();
+    assert_eq!(position, serde_json::from_str::<Position>(&json).unwrap());
+}
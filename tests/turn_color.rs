@@ -0,0 +1,72 @@
+use fen4::{Edge, TurnColor};
+
+#[test]
+fn home_edges() {
+    assert_eq!(TurnColor::Red.home_edge(), Edge::Bottom);
+    assert_eq!(TurnColor::Blue.home_edge(), Edge::Left);
+    assert_eq!(TurnColor::Yellow.home_edge(), Edge::Top);
+    assert_eq!(TurnColor::Green.home_edge(), Edge::Right);
+}
+
+#[test]
+fn prev_undoes_next() {
+    use TurnColor::*;
+    for c in &[Red, Blue, Yellow, Green] {
+        assert_eq!(c.next().prev(), *c);
+        assert_eq!(c.prev().next(), *c);
+    }
+}
+
+#[test]
+fn all_in_index_order() {
+    use fen4::Color;
+    let colors: Vec<TurnColor> = TurnColor::iter().collect();
+    assert_eq!(colors, TurnColor::all().to_vec());
+    for (i, color) in TurnColor::iter().enumerate() {
+        assert_eq!(usize::from(color), i);
+        assert_eq!(Color::from(i), Color::Turn(color));
+        assert_eq!(color.next(), TurnColor::all()[(i + 1) % 4]);
+    }
+    assert_eq!(Color::from(4), Color::Dead(None));
+}
+
+#[test]
+fn teams() {
+    use fen4::Team;
+    use TurnColor::*;
+    assert_eq!(Red.team(), Team::RedYellow);
+    assert_eq!(Blue.team(), Team::BlueGreen);
+    assert_eq!(Yellow.team(), Team::RedYellow);
+    assert_eq!(Green.team(), Team::BlueGreen);
+    for c in TurnColor::iter() {
+        assert_eq!(c.teammate().team(), c.team());
+        assert_ne!(c.teammate(), c);
+        assert_eq!(c.teammate().teammate(), c);
+    }
+}
+
+#[test]
+fn pawn_forward() {
+    use fen4::{Board, Color, Piece, Position};
+    assert_eq!(TurnColor::Red.pawn_forward(), (1, 0));
+    assert_eq!(TurnColor::Blue.pawn_forward(), (0, 1));
+    assert_eq!(TurnColor::Yellow.pawn_forward(), (-1, 0));
+    assert_eq!(TurnColor::Green.pawn_forward(), (0, -1));
+
+    // A step forward from each player's pawns on the default board lands on an empty square in
+    // front of them, away from their home edge
+    let board = Board::default();
+    let pawns = [(1, 3), (3, 1), (12, 3), (3, 12)];
+    for (color, &(row, col)) in TurnColor::iter().zip(&pawns) {
+        assert_eq!(
+            board.board[row][col],
+            Piece::Normal(Color::Turn(color), 'P')
+        );
+        let (dr, dc) = color.pawn_forward();
+        let ahead = Position {
+            row: (row as isize + dr) as usize,
+            col: (col as isize + dc) as usize,
+        };
+        assert!(board.board[ahead.row][ahead.col].is_empty(), "{}", color);
+    }
+}
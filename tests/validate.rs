@@ -0,0 +1,93 @@
+use fen4::{Board, BoardError, Color, Piece, Position, TurnColor};
+
+#[test]
+fn default_is_valid() {
+    assert_eq!(Board::default().validate(), Ok(()));
+    let walled = "R-0,0,0,0-1,1,1,1-1,1,1,1-0,0,0,0-0-X,X,X,8,X,X,X/14/14/14/14/14/14/14/14/14/14/14/14/X,X,X,8,X,X,X";
+    assert_eq!(walled.parse::<Board>().unwrap().validate(), Ok(()));
+}
+
+#[test]
+fn collects_every_problem() {
+    let mut board = Board::default();
+    board.board[0][0] = Piece::Normal(Color::Turn(TurnColor::Red), 'Q');
+    board.dead[1] = true;
+    board.extra_options.royal[0] = vec![Position { row: 5, col: 5 }];
+    board.extra_options.royal[2] = vec![Position { row: 13, col: 6 }];
+    board.extra_options.enpassant[3] =
+        Some((Position { row: 14, col: 1 }, Position { row: 6, col: 11 }));
+    board.extra_options.lives = Some([1, 0, 0, 2]);
+    assert_eq!(
+        board.validate(),
+        Err(vec![
+            BoardError::PieceInCorner(Position { row: 0, col: 0 }),
+            BoardError::OffBoard(Position { row: 14, col: 1 }),
+            BoardError::RoyalNotOwned(TurnColor::Red, Position { row: 5, col: 5 }),
+            BoardError::DeadWithPieces(TurnColor::Blue),
+            BoardError::NoLives(TurnColor::Yellow),
+        ])
+    );
+}
+
+#[test]
+fn sanity_fix() {
+    let mut board = Board {
+        turn: TurnColor::Blue,
+        ..Board::default()
+    };
+    // Blue's en passant is stale since it is Blue's turn again, Green's is still live
+    board.extra_options.enpassant[1] =
+        Some((Position { row: 7, col: 2 }, Position { row: 7, col: 3 }));
+    board.extra_options.enpassant[3] =
+        Some((Position { row: 7, col: 11 }, Position { row: 7, col: 10 }));
+    board.dead[2] = true;
+    // Green's king has moved and Red's queen side rook is gone
+    board.board[6][13] = Piece::Empty;
+    board.board[6][12] = Piece::Normal(Color::Turn(TurnColor::Green), 'K');
+    board.board[0][3] = Piece::Empty;
+    assert_eq!(
+        board.sanity_fix(),
+        vec![
+            "Removed Red's queen side castling",
+            "Cleared Blue's stale en passant",
+            "Turned Yellow's living pieces into dead pieces",
+            "Removed Yellow's king side castling",
+            "Removed Yellow's queen side castling",
+            "Removed Green's king side castling",
+            "Removed Green's queen side castling",
+        ]
+    );
+    assert_eq!(board.extra_options.enpassant[1], None);
+    assert!(board.extra_options.enpassant[3].is_some());
+    assert_eq!(board.castling_king, [true, true, false, false]);
+    assert_eq!(board.castling_queen, [false, true, false, false]);
+    assert_eq!(board.validate(), Ok(()));
+    assert!(board.sanity_fix().is_empty());
+}
+
+#[test]
+fn std2pc() {
+    let red = |shape| Piece::Normal(Color::Turn(TurnColor::Red), shape);
+    let yellow = |shape| Piece::Normal(Color::Turn(TurnColor::Yellow), shape);
+    let mut board = Board::empty_with_walls()
+        .with_piece(Position { row: 0, col: 7 }, red('K'))
+        .with_piece(Position { row: 7, col: 7 }, yellow('K'));
+    assert!(!board.is_std2pc());
+    board.extra_options.std2pc = true;
+    assert!(board.is_std2pc());
+    assert_eq!(board.validate_std2pc(), Ok(()));
+    assert_eq!(board.validate(), Ok(()));
+
+    let outside = Position { row: 9, col: 7 };
+    let blue = Position { row: 4, col: 4 };
+    let board = board
+        .with_piece(outside, yellow('Q'))
+        .with_piece(blue, Piece::Normal(Color::Dead(Some(TurnColor::Blue)), 'N'))
+        .with_piece(Position { row: 12, col: 0 }, Piece::Wall);
+    let expected = vec![
+        BoardError::Std2pcColor(blue, TurnColor::Blue),
+        BoardError::OutsideStd2pcArea(outside),
+    ];
+    assert_eq!(board.validate_std2pc(), Err(expected.clone()));
+    assert_eq!(board.validate(), Err(expected));
+}